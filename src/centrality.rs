@@ -0,0 +1,130 @@
+//! src/centrality.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module implements temporal closeness and betweenness centrality
+//! over a time window `[t_start, t_end)`. Every path leaves its source
+//! no earlier than `t_start` and arrives before `t_end`, following
+//! the path model of [`paths`].
+//!
+//! Betweenness counts shortest foremost paths: a path to `v` qualifies
+//! when each of its prefixes arrives as early as possible at the node it
+//! ends on, and no other such path to `v` has fewer hops. Paths are
+//! sequences of edges, so parallel edges yield distinct paths.
+
+use std::collections::{HashMap, VecDeque};
+use crate::ops::TemporalGraphOps;
+use crate::paths::{self, earliest_traversal};
+use crate::types::TimeSpan;
+
+/// Returns the temporal closeness of `node` over `[t_start, t_end)`.
+///
+/// Every other node reached from `node` contributes `1 / (1 + d)`, where
+/// `d` is how long after `t_start` it is first reached. The sum is
+/// divided by the number of other nodes, so scores lie in `[0, 1]`.
+pub fn temporal_closeness_of<G>(graph: &G, node: &G::Node, t_start: G::Time, t_end: G::Time) -> f64
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    let others = graph.get_nodes().len().saturating_sub(1);
+    if others == 0 || t_end <= t_start {
+        return 0.0;
+    }
+    let harmonic: f64 = paths::arrivals_before(graph, node, t_start, t_end)
+        .iter()
+        .filter(|(reached, _)| *reached != node)
+        .map(|(_, arrival)| 1.0 / (1.0 + arrival.as_f64() - t_start.as_f64()))
+        .sum();
+    harmonic / others as f64
+}
+
+/// Returns the temporal closeness of every node over `[t_start, t_end)`;
+/// see [`temporal_closeness_of`].
+pub fn temporal_closeness<G>(graph: &G, t_start: G::Time, t_end: G::Time) -> HashMap<G::Node, f64>
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    graph
+        .get_nodes()
+        .iter()
+        .map(|node| (node.clone(), temporal_closeness_of(graph, node, t_start, t_end)))
+        .collect()
+}
+
+/// Returns the temporal betweenness of every node over `[t_start, t_end)`:
+/// summed over ordered pairs `(s, v)` of other nodes, the fraction of
+/// shortest foremost paths from `s` to `v` that pass through it.
+pub fn temporal_betweenness<G>(graph: &G, t_start: G::Time, t_end: G::Time) -> HashMap<G::Node, f64>
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    let mut scores: HashMap<G::Node, f64> = graph.get_nodes().iter().map(|node| (node.clone(), 0.0)).collect();
+    for source in graph.get_nodes() {
+        for (node, dependency) in dependencies(graph, source, t_start, t_end) {
+            *scores.entry(node).or_default() += dependency;
+        }
+    }
+    scores
+}
+
+/// Returns the share of the shortest foremost paths from `source` that
+/// every other node lies on, summed over their destinations (Brandes's
+/// dependency accumulation). Nodes with no share are left out.
+pub(crate) fn dependencies<G>(graph: &G, source: &G::Node, t_start: G::Time, t_end: G::Time) -> HashMap<G::Node, f64>
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    if t_end <= t_start || !graph.contains_node(source) {
+        return HashMap::new();
+    }
+    let arrivals = paths::arrivals_before(graph, source, t_start, t_end);
+
+    // Breadth-first search over the edges that reach their destination
+    // at its earliest arrival when taken at their source's.
+    let mut order = Vec::new();
+    let mut depth = HashMap::from([(source.clone(), 0)]);
+    let mut sigma = HashMap::from([(source.clone(), 1.0)]);
+    let mut preds: HashMap<G::Node, Vec<G::Node>> = HashMap::new();
+    let mut queue = VecDeque::from([source.clone()]);
+    while let Some(node) = queue.pop_front() {
+        let ready = arrivals[&node];
+        let next = depth[&node] + 1;
+        let node_lifetime = graph.node_lifetime(&node).unwrap_or_default();
+        for edge in graph.edges_of(&node) {
+            let Some(&earliest) = arrivals.get(&edge.dst) else {
+                continue;
+            };
+            let dst_lifetime = graph.node_lifetime(&edge.dst).unwrap_or_default();
+            let tight = earliest_traversal(edge, ready, &node_lifetime, &dst_lifetime).is_some_and(|(_, arrival)| arrival == earliest);
+            if !tight || &edge.dst == source {
+                continue;
+            }
+            if !depth.contains_key(&edge.dst) {
+                depth.insert(edge.dst.clone(), next);
+                queue.push_back(edge.dst.clone());
+            }
+            if depth[&edge.dst] == next {
+                *sigma.entry(edge.dst.clone()).or_default() += sigma[&node];
+                preds.entry(edge.dst.clone()).or_default().push(node.clone());
+            }
+        }
+        order.push(node);
+    }
+
+    let mut delta: HashMap<G::Node, f64> = HashMap::new();
+    for node in order.iter().rev() {
+        let share = (1.0 + delta.get(node).copied().unwrap_or(0.0)) / sigma[node];
+        for pred in preds.get(node).into_iter().flatten() {
+            *delta.entry(pred.clone()).or_default() += sigma[pred] * share;
+        }
+    }
+    delta.remove(source);
+    delta
+}
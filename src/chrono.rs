@@ -1,118 +1,633 @@
-/// src/chrono.rs
-///
-/// # Author
-/// Yifan Jiang, 2025
-/// alvinjiang01@hotmail.com
-/// GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//! src/chrono.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
 
 use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, Read, Write};
+use std::str::FromStr;
+use std::sync::Arc;
 use rayon::prelude::*;
-
-type NodeId = usize;
-type Timestamp = u64;
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+use crate::edge::{reverse_adjacency, started_before, started_by, TemporalEdge};
+use crate::error::PersistError;
+use crate::ops::TemporalGraphOps;
+use crate::paths::{self, EarliestArrival, FastestPaths, FewestHops, LatestDeparture};
+use crate::reachability::{self, ReachabilityMatrix};
+use crate::components;
+use crate::motifs::{EventIndex, MotifCounts, MOTIF_COUNT};
+use crate::pagerank::{self, PageRankConfig, TemporalPageRank};
+use crate::centrality;
+use crate::cores::{self, CoreEvolution};
+use crate::persist::{self, Codec, Persistent};
+use crate::import::{self, ImportError, ImportOptions, ImportReport, OnError, LineParser};
+use crate::export;
+use crate::events::{self, EventReport};
+use crate::snapshot::{collect_targets, index_nodes, StaticGraph};
+use crate::store::GraphStore;
+use crate::types::{Directionality, NodeId, NodeKey, Time, TimeSpan, Timestamp};
 
 #[derive(Debug, Clone)]
-pub struct ChronoGraph {
-    edges: HashMap<NodeId, Vec<(NodeId, Timestamp)>>,
-    nodes: HashSet<NodeId>,
-    num_threads: usize,
+pub struct ChronoGraph<N = NodeId, T = Timestamp> {
+    store: GraphStore<N, T>,
+    // Pool running this graph's parallel work; `None` uses rayon's global pool.
+    pool: Option<Arc<ThreadPool>>,
+}
+
+impl<N: NodeKey + Send + Sync, T: Time + Send + Sync> Default for ChronoGraph<N, T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl ChronoGraph {
+impl<N: NodeKey + Send + Sync, T: Time + Send + Sync> ChronoGraph<N, T> {
+    /// Creates an empty graph whose parallel work runs on rayon's global pool.
     pub fn new() -> Self {
-        Self {
-            edges: HashMap::new(),
-            nodes: HashSet::new(),
-            num_threads: 1,
-        }
+        Self::new_with_directionality(Directionality::Directed)
     }
 
-    pub fn new_with_threads(num_threads: usize) -> Self {
-        rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build_global()
-            .expect("Failed to build thread pool");
+    /// Creates an empty graph that owns a pool of `num_threads` threads.
+    pub fn new_with_threads(num_threads: usize) -> Result<Self, ThreadPoolBuildError> {
+        let pool = ThreadPoolBuilder::new().num_threads(num_threads).build()?;
+        Ok(Self::new_with_pool(Arc::new(pool)))
+    }
 
-        Self {
-            edges: HashMap::new(),
-            nodes: HashSet::new(),
-            num_threads,
+    /// Creates an empty graph that runs its parallel work on a pool shared
+    /// with other graphs or application code.
+    pub fn new_with_pool(pool: Arc<ThreadPool>) -> Self {
+        let mut graph = Self::new();
+        graph.pool = Some(pool);
+        graph
+    }
+
+    /// Returns the pool owned or shared by this graph, if any.
+    pub fn pool(&self) -> Option<&Arc<ThreadPool>> {
+        self.pool.as_ref()
+    }
+
+    /// Returns the number of threads this graph's parallel work runs on.
+    pub fn num_threads(&self) -> usize {
+        self.install(rayon::current_num_threads)
+    }
+
+    /// Runs `op` inside this graph's pool, so any rayon parallelism it
+    /// uses is confined to that pool.
+    pub fn install<R, OP>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        match &self.pool {
+            Some(pool) => pool.install(op),
+            None => op(),
         }
     }
 
-    pub fn get_nodes(&self) -> &HashSet<NodeId> {
-        &self.nodes
+    /// Creates an empty graph whose edges are symmetric.
+    pub fn new_undirected() -> Self {
+        Self::new_with_directionality(Directionality::Undirected)
     }
 
-    pub fn get_edges(&self) -> &HashMap<NodeId, Vec<(NodeId, Timestamp)>> {
-        &self.edges
+    pub fn new_with_directionality(directionality: Directionality) -> Self {
+        Self { store: GraphStore::new(directionality), pool: None }
     }
 
-    pub fn add_node(&mut self, node_id: NodeId) {
-        self.nodes.insert(node_id);
+    /// Creates an empty graph that maintains an incoming-edge index.
+    pub fn new_with_in_index() -> Self {
+        let mut graph = Self::new();
+        graph.enable_in_index();
+        graph
     }
 
-    pub fn add_edge(&mut self, src: NodeId, dst: NodeId, timestamp: Timestamp) -> Result<(), String> {
-        if !self.nodes.contains(&src) || !self.nodes.contains(&dst) {
-            return Err("Source or destination node does not exist.".into());
-        }
-        self.edges.entry(src).or_insert_with(Vec::new).push((dst, timestamp));
-        Ok(())
+    /// Writes the graph to `writer` in the versioned, checksummed binary
+    /// format described in [`persist`], covering nodes, lifetimes, edges
+    /// and the incoming-edge index setting.
+    pub fn save_to<W: Write>(&self, writer: W) -> Result<(), PersistError>
+    where
+        N: Codec,
+        T: Codec,
+    {
+        persist::write_graph(self, writer)
     }
 
-    pub fn remove_node(&mut self, node_id: NodeId) -> Result<NodeId, bool> {
-        if !self.nodes.remove(&node_id) {
-            return Err(false);
-        }
-    
-        self.edges.remove(&node_id);
-    
-        for edges in self.edges.values_mut() {
-            edges.retain(|(dst, _)| *dst != node_id);
-        }
-    
-        Ok(node_id)
-    }
-    
-    pub fn remove_edge(&mut self, src: NodeId, dst: NodeId, timestamp: Timestamp) -> Result<(NodeId, NodeId, Timestamp), bool> {
-        if let Some(edge_list) = self.edges.get_mut(&src) {
-            let before_len = edge_list.len();
-            edge_list.retain(|(d, t)| !(*d == dst && *t == timestamp));
-            if edge_list.len() < before_len {
-                return Ok((src, dst, timestamp));
+    /// Reads a graph written by [`save_to`](Self::save_to), from either backend.
+    ///
+    /// The loaded graph runs its parallel work on rayon's global pool.
+    pub fn load_from<R: Read>(reader: R) -> Result<Self, PersistError>
+    where
+        N: Codec,
+        T: Codec,
+    {
+        let store = GraphStore::from_image(persist::read_graph(reader)?)?;
+        Ok(Self { store, pool: None })
+    }
+
+    /// Imports the edges of a text edge list, creating missing nodes; see
+    /// [`import_edges`](crate::import::import_edges). Each chunk of lines
+    /// is parsed in parallel, then added in input order.
+    pub fn import_edges<R: BufRead>(&mut self, reader: R, options: &ImportOptions) -> Result<ImportReport, ImportError>
+    where
+        N: FromStr,
+        T: FromStr,
+    {
+        let pool = self.pool.clone();
+        import::import_with(self, reader, options, |parser: &LineParser<'_>, lines: &[(usize, String)]| {
+            let parse = || lines.par_iter().map(|(number, line)| parser.parse(*number, line)).collect();
+            match &pool {
+                Some(pool) => pool.install(parse),
+                None => parse(),
             }
-        }
-        Err(false)
-    }    
-
-    pub fn get_neighbors_at(&self, node: NodeId, timestamp: Timestamp) -> Vec<NodeId> {
-        self.edges
-            .get(&node)
-            .map(|neighbors| {
-                neighbors
-                    .par_iter()
-                    .filter_map(|(dst, ts)| {
-                        if *ts <= timestamp {
-                            Some(*dst)
-                        } else {
-                            None
-                        }
-                    })
-                    .collect()
-            })
-            .unwrap_or_default()
-    }
-
-    pub fn print(&self) {
-        for node in &self.nodes {
-            println!("Node {}:", node);
-            if let Some(edges) = self.edges.get(node) {
-                for (dst, ts) in edges {
-                    println!("  -> {} @ {}", dst, ts);
+        })
+    }
+
+    /// Writes the graph, or the part of it present in `window`, as
+    /// Graphviz DOT; see [`write_dot`](crate::export::write_dot).
+    pub fn write_dot<W: Write>(&self, writer: W, window: Option<(T, T)>) -> io::Result<()> {
+        export::write_dot(self, writer, window)
+    }
+
+    /// Writes the graph, or the part of it present in `window`, as
+    /// GraphML; see [`write_graphml`](crate::export::write_graphml).
+    pub fn write_graphml<W: Write>(&self, writer: W, window: Option<(T, T)>) -> io::Result<()> {
+        export::write_graphml(self, writer, window)
+    }
+
+    /// Writes the graph, or the part of it present in `window`, as
+    /// dynamic GEXF; see [`write_gexf`](crate::export::write_gexf).
+    pub fn write_gexf<W: Write>(&self, writer: W, window: Option<(T, T)>) -> io::Result<()> {
+        export::write_gexf(self, writer, window)
+    }
+
+    /// Applies a JSON Lines stream of graph events, in order; see
+    /// [`read_events`](crate::events::read_events).
+    pub fn read_events<R: BufRead>(&mut self, reader: R, on_error: OnError) -> Result<EventReport, ImportError>
+    where
+        N: FromStr,
+        T: FromStr + TimeSpan,
+    {
+        events::read_events(self, reader, on_error)
+    }
+
+    /// Writes the JSON Lines events that rebuild the graph; see
+    /// [`write_events`](crate::events::write_events).
+    pub fn write_events<W: Write>(&self, writer: W) -> io::Result<()> {
+        events::write_events(self, writer)
+    }
+
+    /// Computes the earliest time every node can be reached from `source`
+    /// leaving at `start_time`; see [`paths::earliest_arrival`].
+    pub fn earliest_arrival(&self, source: N, start_time: T) -> EarliestArrival<N, T>
+    where
+        T: TimeSpan,
+    {
+        paths::earliest_arrival(self, source, start_time)
+    }
+
+    /// Computes how late every node can be left to reach `target` by
+    /// `deadline`; see [`paths::latest_departure`].
+    pub fn latest_departure(&self, target: N, deadline: T) -> LatestDeparture<N, T>
+    where
+        T: TimeSpan,
+    {
+        paths::latest_departure(self, target, deadline)
+    }
+
+    /// Computes the minimum journey duration from `source` to every node;
+    /// see [`paths::fastest_paths`].
+    pub fn fastest_paths(&self, source: N, start_time: T) -> FastestPaths<N, T>
+    where
+        T: TimeSpan,
+    {
+        paths::fastest_paths(self, source, start_time)
+    }
+
+    /// Computes the fewest hops needed to reach every node from `source`;
+    /// see [`paths::fewest_hops`].
+    pub fn fewest_hops(&self, source: N, start_time: T) -> FewestHops<N, T>
+    where
+        T: TimeSpan,
+    {
+        paths::fewest_hops(self, source, start_time)
+    }
+
+    /// Returns the nodes `node` can reach within `[t_start, t_end)`; see
+    /// [`reachability::reachable_from`].
+    pub fn reachable_from(&self, node: N, t_start: T, t_end: T) -> HashSet<N>
+    where
+        T: TimeSpan,
+    {
+        reachability::reachable_from(self, node, t_start, t_end)
+    }
+
+    /// Runs [`earliest_arrival`](Self::earliest_arrival) from every source
+    /// in parallel, returning the results in the order of `sources`.
+    pub fn earliest_arrival_many(&self, sources: &[N], start_time: T) -> Vec<EarliestArrival<N, T>>
+    where
+        T: TimeSpan,
+    {
+        self.install(|| {
+            sources
+                .par_iter()
+                .map(|source| paths::earliest_arrival(self, source.clone(), start_time))
+                .collect()
+        })
+    }
+
+    /// Runs [`latest_departure`](Self::latest_departure) towards every
+    /// target in parallel, returning the results in the order of `targets`.
+    /// The incoming-edge index is reused when enabled.
+    pub fn latest_departure_many(&self, targets: &[N], deadline: T) -> Vec<LatestDeparture<N, T>>
+    where
+        T: TimeSpan,
+    {
+        self.install(|| {
+            let built;
+            let reversed = match &self.store.in_edges {
+                Some(in_edges) => in_edges,
+                None => {
+                    built = reverse_adjacency(&self.store.edges);
+                    &built
                 }
-            } else {
-                println!("  (no outgoing edges)");
+            };
+            targets
+                .par_iter()
+                .map(|target| paths::latest_departure_in(self, reversed, target.clone(), deadline))
+                .collect()
+        })
+    }
+
+    /// Runs [`fastest_paths`](Self::fastest_paths) from every source in
+    /// parallel, returning the results in the order of `sources`.
+    pub fn fastest_paths_many(&self, sources: &[N], start_time: T) -> Vec<FastestPaths<N, T>>
+    where
+        T: TimeSpan,
+    {
+        self.install(|| {
+            sources
+                .par_iter()
+                .map(|source| paths::fastest_paths(self, source.clone(), start_time))
+                .collect()
+        })
+    }
+
+    /// Runs [`fewest_hops`](Self::fewest_hops) from every source in
+    /// parallel, returning the results in the order of `sources`.
+    pub fn fewest_hops_many(&self, sources: &[N], start_time: T) -> Vec<FewestHops<N, T>>
+    where
+        T: TimeSpan,
+    {
+        self.install(|| {
+            sources
+                .par_iter()
+                .map(|source| paths::fewest_hops(self, source.clone(), start_time))
+                .collect()
+        })
+    }
+
+    /// Computes the reachability relation within `[t_start, t_end)` from
+    /// every node in parallel, one bitset row per source.
+    pub fn reachability_matrix(&self, t_start: T, t_end: T) -> ReachabilityMatrix<N>
+    where
+        T: TimeSpan,
+    {
+        self.install(|| {
+            let nodes: Vec<N> = self.store.nodes.iter().cloned().collect();
+            let index = index_nodes(&nodes);
+            let rows = nodes
+                .par_iter()
+                .map(|source| reachability::reachability_row(self, &index, source, t_start, t_end))
+                .collect();
+            ReachabilityMatrix::from_rows(nodes, index, rows)
+        })
+    }
+
+    /// Counts the nodes every node reaches within `[t_start, t_end)`, in
+    /// parallel and without materializing the full relation.
+    pub fn reachability_counts(&self, t_start: T, t_end: T) -> HashMap<N, usize>
+    where
+        T: TimeSpan,
+    {
+        self.install(|| {
+            self.store.nodes
+                .par_iter()
+                .map(|source| (source.clone(), reachability::reachable_from(self, source.clone(), t_start, t_end).len()))
+                .collect()
+        })
+    }
+
+    /// Returns the weakly connected component id of every node alive at
+    /// `timestamp`, labelling the snapshot in parallel; see
+    /// [`components::weak_components_at`].
+    pub fn weak_components_at(&self, timestamp: T) -> HashMap<N, usize> {
+        self.install(|| components::par_static_weak_components(&self.snapshot_at(timestamp)))
+    }
+
+    /// Returns the strongly temporally connected component id of every
+    /// node over `[t_start, t_end)`, computing the reachability relation
+    /// in parallel; see [`components::strong_temporal_components`].
+    pub fn strong_temporal_components(&self, t_start: T, t_end: T) -> HashMap<N, usize>
+    where
+        T: TimeSpan,
+    {
+        components::mutual_components(&self.reachability_matrix(t_start, t_end))
+    }
+
+    /// Counts the instances of every 3-edge δ-temporal motif, sweeping
+    /// the events at each node in parallel; see
+    /// [`count_motifs`](crate::motifs::count_motifs).
+    pub fn count_motifs(&self, delta: T) -> MotifCounts
+    where
+        T: TimeSpan,
+    {
+        self.install(|| {
+            let events = EventIndex::new(self);
+            let counts = (0..events.node_count())
+                .into_par_iter()
+                .fold(
+                    || [0; MOTIF_COUNT],
+                    |mut counts, node| {
+                        events.count_from(node, delta, &mut counts);
+                        counts
+                    },
+                )
+                .reduce(
+                    || [0; MOTIF_COUNT],
+                    |mut left, right| {
+                        left.iter_mut().zip(right).for_each(|(total, count)| *total += count);
+                        left
+                    },
+                );
+            MotifCounts::from_array(counts)
+        })
+    }
+
+    /// Computes temporal PageRank over the edges that started by
+    /// `timestamp`. Collecting, sorting and normalizing run in parallel;
+    /// the edge stream itself is processed in time order. See
+    /// [`temporal_pagerank`](crate::pagerank::temporal_pagerank).
+    pub fn temporal_pagerank(&self, timestamp: T, config: PageRankConfig) -> HashMap<N, f64> {
+        self.install(|| {
+            let mut events: Vec<(T, &N, &N)> = self
+                .store.edges
+                .par_iter()
+                .flat_map_iter(|(src, edges)| started_by(edges, timestamp).iter().map(move |edge| (edge.interval.start, src, &edge.dst)))
+                .collect();
+            events.par_sort_by_cached_key(pagerank::event_order);
+
+            let mut pagerank = TemporalPageRank::new(config);
+            for (_, src, dst) in events {
+                pagerank.process_edge(src, dst);
+            }
+            let raw = pagerank.raw_scores();
+            let total: f64 = raw.par_iter().map(|(_, rank)| rank).sum();
+            self.store.nodes
+                .par_iter()
+                .map(|node| {
+                    let rank = raw.get(node).copied().unwrap_or(0.0);
+                    (node.clone(), if total > 0.0 { rank / total } else { 0.0 })
+                })
+                .collect()
+        })
+    }
+
+    /// Returns the temporal closeness of every node over `[t_start, t_end)`,
+    /// one search per node in parallel; see
+    /// [`centrality::temporal_closeness_of`].
+    pub fn temporal_closeness(&self, t_start: T, t_end: T) -> HashMap<N, f64>
+    where
+        T: TimeSpan,
+    {
+        self.install(|| {
+            self.store.nodes
+                .par_iter()
+                .map(|node| (node.clone(), centrality::temporal_closeness_of(self, node, t_start, t_end)))
+                .collect()
+        })
+    }
+
+    /// Returns the temporal betweenness of every node over `[t_start, t_end)`,
+    /// accumulating the dependencies of every source in parallel; see
+    /// [`centrality::temporal_betweenness`].
+    pub fn temporal_betweenness(&self, t_start: T, t_end: T) -> HashMap<N, f64>
+    where
+        T: TimeSpan,
+    {
+        self.install(|| {
+            let mut scores = self
+                .store.nodes
+                .par_iter()
+                .fold(HashMap::new, |mut scores: HashMap<N, f64>, source| {
+                    for (node, dependency) in centrality::dependencies(self, source, t_start, t_end) {
+                        *scores.entry(node).or_default() += dependency;
+                    }
+                    scores
+                })
+                .reduce(HashMap::new, |mut left, right| {
+                    for (node, dependency) in right {
+                        *left.entry(node).or_default() += dependency;
+                    }
+                    left
+                });
+            for node in &self.store.nodes {
+                scores.entry(node.clone()).or_insert(0.0);
+            }
+            scores
+        })
+    }
+
+    /// Returns the core number of every node present in `[t_start, t_end)`,
+    /// building the window's snapshot in parallel; see
+    /// [`cores::core_numbers`].
+    pub fn core_numbers(&self, t_start: T, t_end: T) -> HashMap<N, usize> {
+        cores::static_core_numbers(&self.snapshot_between(t_start, t_end))
+    }
+
+    /// Computes the core numbers in each of `windows`, decomposing the
+    /// windows in parallel; see [`cores::core_evolution`].
+    pub fn core_evolution(&self, windows: &[(T, T)]) -> CoreEvolution<N, T> {
+        let cores = self.install(|| {
+            windows
+                .par_iter()
+                .map(|&(t_start, t_end)| cores::static_core_numbers(&self.snapshot_between(t_start, t_end)))
+                .collect()
+        });
+        CoreEvolution::new(windows.to_vec(), cores)
+    }
+}
+
+/// Runs the queries that scan many edges or nodes in parallel on the
+/// graph's pool; everything else is provided by [`TemporalGraphOps`].
+impl<N: NodeKey + Send + Sync, T: Time + Send + Sync> TemporalGraphOps for ChronoGraph<N, T> {
+    type Node = N;
+    type Time = T;
+
+    fn store(&self) -> &GraphStore<N, T> {
+        &self.store
+    }
+
+    fn store_mut(&mut self) -> &mut GraphStore<N, T> {
+        &mut self.store
+    }
+
+    fn edge_count(&self) -> usize {
+        let edges = &self.store.edges;
+        self.install(|| {
+            let entries: usize = edges.par_iter().map(|(_, edges)| edges.len()).sum();
+            if self.is_directed() {
+                return entries;
             }
+            // Undirected self-loops are the only edges stored a single time.
+            let self_loops: usize = edges
+                .par_iter()
+                .map(|(node, edges)| edges.iter().filter(|edge| &edge.dst == node).count())
+                .sum();
+            (entries + self_loops) / 2
+        })
+    }
+
+    fn in_degree(&self, node: &N) -> usize {
+        if !self.is_directed() {
+            return self.out_degree(node);
+        }
+        if self.has_in_index() {
+            return self.store.in_degree(node);
+        }
+        self.install(|| {
+            self.store
+                .edges
+                .par_iter()
+                .map(|(_, edges)| edges.iter().filter(|edge| &edge.dst == node).count())
+                .sum()
+        })
+    }
+
+    fn nodes_at(&self, timestamp: T) -> Vec<N> {
+        self.install(|| {
+            self.store
+                .nodes
+                .par_iter()
+                .filter(|node| self.store.is_alive_at(node, timestamp))
+                .cloned()
+                .collect()
+        })
+    }
+
+    fn get_neighbors_at(&self, node: N, timestamp: T) -> Vec<N> {
+        self.get_neighbors_at_filtered(node, timestamp, |_| true)
+    }
+
+    fn get_edges_at(&self, node: N, timestamp: T) -> Vec<&TemporalEdge<N, T>> {
+        let store = &self.store;
+        self.install(|| {
+            store
+                .started_edges(&store.edges, &node, timestamp)
+                .par_iter()
+                .filter(|edge| store.is_visible(edge, timestamp))
+                .collect()
+        })
+    }
+
+    fn get_neighbors_at_filtered<F>(&self, node: N, timestamp: T, filter: F) -> Vec<N>
+    where
+        F: Fn(&TemporalEdge<N, T>) -> bool + Sync,
+    {
+        let store = &self.store;
+        self.install(|| {
+            store
+                .started_edges(&store.edges, &node, timestamp)
+                .par_iter()
+                .filter(|edge| store.is_visible(edge, timestamp) && filter(edge))
+                .map(|edge| edge.dst.clone())
+                .collect()
+        })
+    }
+
+    fn get_in_neighbors_at(&self, node: N, timestamp: T) -> Vec<N> {
+        if !self.is_directed() {
+            return self.get_neighbors_at(node, timestamp);
         }
+        let store = &self.store;
+        self.install(|| match &store.in_edges {
+            Some(in_edges) => store
+                .started_edges(in_edges, &node, timestamp)
+                .par_iter()
+                .filter(|edge| store.is_visible(edge, timestamp))
+                .map(|edge| edge.dst.clone())
+                .collect(),
+            None if !store.is_alive_at(&node, timestamp) => Vec::new(),
+            None => store
+                .edges
+                .par_iter()
+                .filter(|(src, _)| store.within_lifetime(src, timestamp))
+                .flat_map_iter(|(src, neighbors)| {
+                    started_by(neighbors, timestamp)
+                        .iter()
+                        .filter(|edge| edge.dst == node && edge.is_active_at(timestamp))
+                        .map(move |_| src.clone())
+                })
+                .collect(),
+        })
     }
-}
\ No newline at end of file
+
+    fn edges_in_range(&self, t_start: T, t_end: T) -> Vec<(N, &TemporalEdge<N, T>)> {
+        let store = &self.store;
+        self.install(|| {
+            store
+                .edges
+                .par_iter()
+                .flat_map_iter(|(src, edges)| store.edges_from_in_range(src, edges, t_start, t_end))
+                .collect()
+        })
+    }
+
+    /// Builds each node's neighbor list in parallel.
+    fn snapshot_at(&self, timestamp: T) -> StaticGraph<N> {
+        let store = &self.store;
+        self.install(|| {
+            let nodes = self.nodes_at(timestamp);
+            let index = index_nodes(&nodes);
+            let lists = nodes
+                .par_iter()
+                .map(|node| {
+                    let edges = store.started_edges(&store.edges, node, timestamp);
+                    collect_targets(edges, &index, |edge| edge.is_active_at(timestamp))
+                })
+                .collect();
+            StaticGraph::from_lists(nodes, index, lists, store.directionality)
+        })
+    }
+
+    /// Builds each node's neighbor list in parallel.
+    fn snapshot_between(&self, t_start: T, t_end: T) -> StaticGraph<N> {
+        let store = &self.store;
+        self.install(|| {
+            let nodes: Vec<N> = store
+                .nodes
+                .par_iter()
+                .filter(|node| store.overlaps_lifetime(node, t_start, t_end))
+                .cloned()
+                .collect();
+            let index = index_nodes(&nodes);
+            let lists = nodes
+                .par_iter()
+                .map(|node| {
+                    let edges = store.edges.get(node).map(|edges| started_before(edges, t_end)).unwrap_or(&[]);
+                    collect_targets(edges, &index, |edge| edge.interval.overlaps(t_start, t_end))
+                })
+                .collect();
+            StaticGraph::from_lists(nodes, index, lists, store.directionality)
+        })
+    }
+}
+
+impl<N: NodeKey + Codec + Send + Sync, T: Time + Codec + Send + Sync> Persistent for ChronoGraph<N, T> {
+    fn save_to<W: Write>(&self, writer: W) -> Result<(), PersistError> {
+        Self::save_to(self, writer)
+    }
+
+    fn load_from<R: Read>(reader: R) -> Result<Self, PersistError> {
+        Self::load_from(reader)
+    }
+}
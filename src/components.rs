@@ -0,0 +1,160 @@
+//! src/components.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module implements connected components of temporal graphs:
+//! weakly connected components of the snapshot at a given time, and
+//! strongly temporally connected components, built from mutual
+//! time-respecting reachability over a window.
+//!
+//! Every function returns a component id per node. Ids are dense,
+//! starting at `0`, but which component gets which id is unspecified.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use rayon::prelude::*;
+use crate::ops::TemporalGraphOps;
+use crate::reachability::{self, ReachabilityMatrix};
+use crate::snapshot::StaticGraph;
+use crate::types::{NodeKey, TimeSpan};
+
+/// Union-find over dense indices with path halving and union by size.
+pub(crate) struct DisjointSets {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl DisjointSets {
+    pub(crate) fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+        }
+    }
+
+    pub(crate) fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    pub(crate) fn union(&mut self, a: usize, b: usize) {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+    }
+
+    /// Returns a dense component id per index, numbered in order of
+    /// each component's first index.
+    pub(crate) fn labels(&mut self) -> Vec<usize> {
+        let roots: Vec<usize> = (0..self.parent.len()).map(|i| self.find(i)).collect();
+        dense_labels(&roots)
+    }
+}
+
+/// Renumbers arbitrary representative labels to `0..k` in order of first appearance.
+pub(crate) fn dense_labels(representatives: &[usize]) -> Vec<usize> {
+    let mut ids = HashMap::new();
+    representatives
+        .iter()
+        .map(|&rep| {
+            let next = ids.len();
+            *ids.entry(rep).or_insert(next)
+        })
+        .collect()
+}
+
+/// Pairs every node of `nodes` with its entry in `labels`.
+pub(crate) fn label_nodes<N: NodeKey>(nodes: &[N], labels: Vec<usize>) -> HashMap<N, usize> {
+    nodes.iter().cloned().zip(labels).collect()
+}
+
+/// Returns the weakly connected components of `snapshot`, treating every
+/// edge as undirected.
+pub fn static_weak_components<N: NodeKey>(snapshot: &StaticGraph<N>) -> HashMap<N, usize> {
+    let mut sets = DisjointSets::new(snapshot.node_count());
+    for i in 0..snapshot.node_count() {
+        for &j in snapshot.neighbors(i) {
+            sets.union(i, j);
+        }
+    }
+    label_nodes(snapshot.nodes(), sets.labels())
+}
+
+/// Parallel [`static_weak_components`] by min-label propagation: every
+/// round lowers the labels at both ends of each edge to the smaller one
+/// and then jumps labels to their label's label, until nothing changes.
+pub(crate) fn par_static_weak_components<N: NodeKey + Send + Sync>(snapshot: &StaticGraph<N>) -> HashMap<N, usize> {
+    let labels: Vec<AtomicUsize> = (0..snapshot.node_count()).map(AtomicUsize::new).collect();
+    loop {
+        let changed = AtomicBool::new(false);
+        (0..snapshot.node_count()).into_par_iter().for_each(|i| {
+            for &j in snapshot.neighbors(i) {
+                let (li, lj) = (labels[i].load(Ordering::Relaxed), labels[j].load(Ordering::Relaxed));
+                if li < lj && labels[j].fetch_min(li, Ordering::Relaxed) > li
+                    || lj < li && labels[i].fetch_min(lj, Ordering::Relaxed) > lj
+                {
+                    changed.store(true, Ordering::Relaxed);
+                }
+            }
+        });
+        (0..snapshot.node_count()).into_par_iter().for_each(|i| {
+            let label = labels[i].load(Ordering::Relaxed);
+            let jumped = labels[label].load(Ordering::Relaxed);
+            if jumped < label && labels[i].fetch_min(jumped, Ordering::Relaxed) > jumped {
+                changed.store(true, Ordering::Relaxed);
+            }
+        });
+        if !changed.into_inner() {
+            break;
+        }
+    }
+    let representatives: Vec<usize> = labels.into_iter().map(AtomicUsize::into_inner).collect();
+    label_nodes(snapshot.nodes(), dense_labels(&representatives))
+}
+
+/// Returns the weakly connected components of the graph as it is at
+/// `timestamp`. Only nodes alive at `timestamp` are labelled.
+pub fn weak_components_at<G: TemporalGraphOps>(graph: &G, timestamp: G::Time) -> HashMap<G::Node, usize> {
+    static_weak_components(&graph.snapshot_at(timestamp))
+}
+
+/// Groups nodes that reach each other within a window, given the
+/// reachability relation of that window.
+///
+/// Two nodes share a component when they are linked by a chain of node
+/// pairs that reach each other. Temporal reachability is not transitive,
+/// so members of a component need not all reach each other directly;
+/// finding maximal sets that do is NP-hard.
+pub fn mutual_components<N: NodeKey>(matrix: &ReachabilityMatrix<N>) -> HashMap<N, usize> {
+    let mut sets = DisjointSets::new(matrix.node_count());
+    for i in 0..matrix.node_count() {
+        for j in matrix.reached_indices(i).filter(|&j| j > i) {
+            if matrix.reaches(j, i) {
+                sets.union(i, j);
+            }
+        }
+    }
+    label_nodes(matrix.nodes(), sets.labels())
+}
+
+/// Returns the strongly temporally connected components over
+/// `[t_start, t_end)`; see [`mutual_components`].
+pub fn strong_temporal_components<G>(graph: &G, t_start: G::Time, t_end: G::Time) -> HashMap<G::Node, usize>
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    mutual_components(&reachability::reachability_matrix(graph, t_start, t_end))
+}
@@ -0,0 +1,198 @@
+//! src/cores.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module implements k-core decomposition of the graph active in a
+//! time window, and [`CoreEvolution`], which follows every node's core
+//! number across a sequence of windows.
+//!
+//! Cores are computed on the underlying simple undirected graph of each
+//! window: direction, parallel edges and self-loops are ignored.
+
+use std::collections::{HashMap, HashSet};
+use crate::ops::TemporalGraphOps;
+use crate::snapshot::StaticGraph;
+use crate::types::{NodeId, NodeKey, Time, TimeSpan, Timestamp};
+
+/// Core numbers of the nodes over a sequence of time windows.
+#[derive(Debug, Clone)]
+pub struct CoreEvolution<N = NodeId, T = Timestamp> {
+    windows: Vec<(T, T)>,
+    cores: Vec<HashMap<N, usize>>,
+}
+
+impl<N: NodeKey, T: Time> CoreEvolution<N, T> {
+    pub(crate) fn new(windows: Vec<(T, T)>, cores: Vec<HashMap<N, usize>>) -> Self {
+        Self { windows, cores }
+    }
+
+    /// Returns the number of windows.
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Returns the windows as `[t_start, t_end)` pairs, in the order given.
+    pub fn windows(&self) -> &[(T, T)] {
+        &self.windows
+    }
+
+    /// Returns the core number of every node present in window `index`.
+    pub fn core_numbers(&self, index: usize) -> &HashMap<N, usize> {
+        &self.cores[index]
+    }
+
+    /// Returns the core number of `node` in window `index`, or `None` if
+    /// the node is not present in that window.
+    pub fn core_number(&self, index: usize, node: &N) -> Option<usize> {
+        self.cores[index].get(node).copied()
+    }
+
+    /// Returns the core number of `node` in every window.
+    pub fn trajectory(&self, node: &N) -> Vec<Option<usize>> {
+        self.cores.iter().map(|cores| cores.get(node).copied()).collect()
+    }
+
+    /// Returns the nodes in the `k`-core of window `index` that were not
+    /// in the `k`-core of the window before it. Every member of the first
+    /// window's `k`-core counts as joining.
+    pub fn joined_core(&self, index: usize, k: usize) -> Vec<&N> {
+        let before = index.checked_sub(1).map(|prev| &self.cores[prev]);
+        self.cores[index]
+            .iter()
+            .filter(|&(node, &core)| core >= k && before.is_none_or(|before| before.get(node).is_none_or(|&old| old < k)))
+            .map(|(node, _)| node)
+            .collect()
+    }
+
+    /// Returns the nodes in the `k`-core of the window before `index` that
+    /// are not in the `k`-core of window `index`.
+    pub fn left_core(&self, index: usize, k: usize) -> Vec<&N> {
+        let Some(prev) = index.checked_sub(1) else {
+            return Vec::new();
+        };
+        let now = &self.cores[index];
+        self.cores[prev]
+            .iter()
+            .filter(|&(node, &core)| core >= k && now.get(node).is_none_or(|&new| new < k))
+            .map(|(node, _)| node)
+            .collect()
+    }
+}
+
+/// Returns the core number of every node of `snapshot` (Batagelj and
+/// Zaversnik's bucket algorithm, linear in the size of the graph).
+pub fn static_core_numbers<N: NodeKey>(snapshot: &StaticGraph<N>) -> HashMap<N, usize> {
+    let adjacency = simple_undirected(snapshot);
+    let n = adjacency.len();
+    let mut degree: Vec<usize> = adjacency.iter().map(Vec::len).collect();
+    let max_degree = degree.iter().copied().max().unwrap_or(0);
+
+    // `order` holds the nodes sorted by current degree; `bin[d]` is where
+    // the nodes of degree `d` begin and `position` locates each node.
+    let mut bin = vec![0; max_degree + 1];
+    for &d in &degree {
+        bin[d] += 1;
+    }
+    let mut start = 0;
+    for count in bin.iter_mut() {
+        let size = *count;
+        *count = start;
+        start += size;
+    }
+    let mut position = vec![0; n];
+    let mut order = vec![0; n];
+    for node in 0..n {
+        position[node] = bin[degree[node]];
+        order[position[node]] = node;
+        bin[degree[node]] += 1;
+    }
+    for d in (1..=max_degree).rev() {
+        bin[d] = bin[d - 1];
+    }
+    bin[0] = 0;
+
+    for i in 0..n {
+        let node = order[i];
+        for &neighbor in &adjacency[node] {
+            if degree[neighbor] > degree[node] {
+                // Swap `neighbor` to the front of its bucket, then shrink it by one.
+                let d = degree[neighbor];
+                let front = order[bin[d]];
+                if front != neighbor {
+                    order.swap(position[neighbor], bin[d]);
+                    position[front] = position[neighbor];
+                    position[neighbor] = bin[d];
+                }
+                bin[d] += 1;
+                degree[neighbor] -= 1;
+            }
+        }
+    }
+    snapshot.nodes().iter().cloned().zip(degree).collect()
+}
+
+/// Returns the core number of every node present in `[t_start, t_end)`.
+pub fn core_numbers<G: TemporalGraphOps>(graph: &G, t_start: G::Time, t_end: G::Time) -> HashMap<G::Node, usize> {
+    static_core_numbers(&graph.snapshot_between(t_start, t_end))
+}
+
+/// Returns the nodes of the `k`-core of the graph active in `[t_start, t_end)`.
+pub fn k_core<G: TemporalGraphOps>(graph: &G, k: usize, t_start: G::Time, t_end: G::Time) -> HashSet<G::Node> {
+    core_numbers(graph, t_start, t_end)
+        .into_iter()
+        .filter(|&(_, core)| core >= k)
+        .map(|(node, _)| node)
+        .collect()
+}
+
+/// Computes the core numbers in each of `windows`, given as
+/// `[t_start, t_end)` pairs.
+pub fn core_evolution<G: TemporalGraphOps>(graph: &G, windows: &[(G::Time, G::Time)]) -> CoreEvolution<G::Node, G::Time> {
+    let cores = windows.iter().map(|&(t_start, t_end)| core_numbers(graph, t_start, t_end)).collect();
+    CoreEvolution::new(windows.to_vec(), cores)
+}
+
+/// Returns consecutive windows of length `width` starting every `step`
+/// from `start`, covering up to `end`. The last window may extend past `end`.
+pub fn sliding_windows<T: TimeSpan>(start: T, end: T, width: T, step: T) -> Vec<(T, T)> {
+    let mut windows = Vec::new();
+    if step <= T::ZERO {
+        return windows;
+    }
+    let mut t = start;
+    while t < end {
+        let Some(window_end) = t.checked_add(width) else {
+            break;
+        };
+        windows.push((t, window_end));
+        let Some(next) = t.checked_add(step) else {
+            break;
+        };
+        t = next;
+    }
+    windows
+}
+
+/// Returns the sorted, deduplicated neighbors of every node of
+/// `snapshot` with edges taken as undirected and self-loops dropped.
+fn simple_undirected<N: NodeKey>(snapshot: &StaticGraph<N>) -> Vec<Vec<usize>> {
+    let mut adjacency = vec![Vec::new(); snapshot.node_count()];
+    for i in 0..snapshot.node_count() {
+        for &j in snapshot.neighbors(i).iter().filter(|&&j| j != i) {
+            adjacency[i].push(j);
+            adjacency[j].push(i);
+        }
+    }
+    for neighbors in &mut adjacency {
+        neighbors.sort_unstable();
+        neighbors.dedup();
+    }
+    adjacency
+}
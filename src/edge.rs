@@ -0,0 +1,324 @@
+//! src/edge.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module defines the temporal edge representation shared by
+//! [`TemporalGraph`](crate::sequential::TemporalGraph) and
+//! [`ChronoGraph`](crate::chrono::ChronoGraph).
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use crate::types::{NodeId, NodeKey, Time, Timestamp};
+
+/// A half-open validity interval `[start, end)`.
+///
+/// An interval without an `end` is open-ended: it stays active from
+/// `start` onwards, which is what a plain timestamped edge means.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T = Timestamp> {
+    pub start: T,
+    pub end: Option<T>,
+}
+
+impl<T: Time> Interval<T> {
+    /// Creates the bounded interval `[start, end)`.
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end: Some(end) }
+    }
+
+    /// Creates the open-ended interval `[start, ∞)`.
+    pub fn starting_at(start: T) -> Self {
+        Self { start, end: None }
+    }
+
+    /// Returns `true` if the interval has no end.
+    pub fn is_open(&self) -> bool {
+        self.end.is_none()
+    }
+
+    /// Returns `true` if `timestamp` lies within `[start, end)`.
+    pub fn contains(&self, timestamp: T) -> bool {
+        self.start <= timestamp && self.end.is_none_or(|end| timestamp < end)
+    }
+
+    /// Returns `true` if the interval shares any time with `[start, end)`.
+    pub fn overlaps(&self, start: T, end: T) -> bool {
+        start < end && self.start < end && self.end.is_none_or(|own_end| start < own_end)
+    }
+}
+
+impl<T: Time> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.end {
+            Some(end) => write!(f, "[{}, {})", self.start, end),
+            None => write!(f, "[{}, ∞)", self.start),
+        }
+    }
+}
+
+/// The existence interval `[born, died)` of a node.
+///
+/// A missing bound is unbounded, so the default lifetime covers all time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Lifetime<T = Timestamp> {
+    pub born: Option<T>,
+    pub died: Option<T>,
+}
+
+impl<T> Default for Lifetime<T> {
+    fn default() -> Self {
+        Self { born: None, died: None }
+    }
+}
+
+impl<T: Time> Lifetime<T> {
+    /// Creates the lifetime `[born, ∞)`.
+    pub fn starting_at(born: T) -> Self {
+        Self { born: Some(born), died: None }
+    }
+
+    /// Returns `true` if the node exists at `timestamp`.
+    pub fn contains(&self, timestamp: T) -> bool {
+        self.born.is_none_or(|born| born <= timestamp) && self.died.is_none_or(|died| timestamp < died)
+    }
+
+    /// Returns `true` if the node exists at some point within `[start, end)`.
+    pub fn overlaps(&self, start: T, end: T) -> bool {
+        start < end && self.born.is_none_or(|born| born < end) && self.died.is_none_or(|died| start < died)
+    }
+}
+
+impl<T: Time> fmt::Display for Lifetime<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.born {
+            Some(born) => write!(f, "[{}, ", born)?,
+            None => write!(f, "(-∞, ")?,
+        }
+        match self.died {
+            Some(died) => write!(f, "{})", died),
+            None => write!(f, "∞)"),
+        }
+    }
+}
+
+/// A typed value attached to an edge under a property key.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+    Str(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+}
+
+impl PropertyValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            PropertyValue::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            PropertyValue::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a float, widening integers.
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            PropertyValue::Float(value) => Some(*value),
+            PropertyValue::Int(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            PropertyValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PropertyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyValue::Str(value) => write!(f, "{:?}", value),
+            PropertyValue::Int(value) => write!(f, "{}", value),
+            PropertyValue::Float(value) => write!(f, "{}", value),
+            PropertyValue::Bool(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl From<&str> for PropertyValue {
+    fn from(value: &str) -> Self {
+        PropertyValue::Str(value.to_string())
+    }
+}
+
+impl From<String> for PropertyValue {
+    fn from(value: String) -> Self {
+        PropertyValue::Str(value)
+    }
+}
+
+impl From<i64> for PropertyValue {
+    fn from(value: i64) -> Self {
+        PropertyValue::Int(value)
+    }
+}
+
+impl From<f64> for PropertyValue {
+    fn from(value: f64) -> Self {
+        PropertyValue::Float(value)
+    }
+}
+
+impl From<bool> for PropertyValue {
+    fn from(value: bool) -> Self {
+        PropertyValue::Bool(value)
+    }
+}
+
+/// Small property map attached to an edge, ordered by key.
+pub type Properties = BTreeMap<String, PropertyValue>;
+
+/// Weight given to edges added without an explicit one.
+pub const DEFAULT_WEIGHT: f64 = 1.0;
+
+/// An outgoing edge stored in a node's adjacency list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemporalEdge<N = NodeId, T = Timestamp> {
+    pub dst: N,
+    pub interval: Interval<T>,
+    pub weight: f64,
+    pub properties: Properties,
+    /// Time needed to traverse the edge; `None` means instantaneous.
+    pub duration: Option<T>,
+    // Set on the copy an undirected graph stores under the destination.
+    pub(crate) mirror: bool,
+}
+
+impl<N: NodeKey, T: Time> TemporalEdge<N, T> {
+    /// Creates an edge with the default weight and no properties.
+    pub fn new(dst: N, interval: Interval<T>) -> Self {
+        Self {
+            dst,
+            interval,
+            weight: DEFAULT_WEIGHT,
+            properties: Properties::new(),
+            duration: None,
+            mirror: false,
+        }
+    }
+
+    /// Returns `true` for the second copy an undirected graph stores under
+    /// the destination node. Skipping mirrors visits each edge once.
+    pub fn is_mirror(&self) -> bool {
+        self.mirror
+    }
+
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn with_property(mut self, key: impl Into<String>, value: impl Into<PropertyValue>) -> Self {
+        self.properties.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn property(&self, key: &str) -> Option<&PropertyValue> {
+        self.properties.get(key)
+    }
+
+    pub fn with_duration(mut self, duration: T) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Returns the reverse-index entry for this edge leaving `src`: its
+    /// `dst` is `src`, and it keeps the interval, weight and duration
+    /// but not the properties.
+    pub(crate) fn reversed(&self, src: N) -> Self {
+        let mut reverse = TemporalEdge::new(src, self.interval).with_weight(self.weight);
+        reverse.duration = self.duration;
+        reverse
+    }
+
+    /// Returns `true` if the edge is active at `timestamp`.
+    pub fn is_active_at(&self, timestamp: T) -> bool {
+        self.interval.contains(timestamp)
+    }
+}
+
+impl<N: NodeKey, T: Time> fmt::Display for TemporalEdge<N, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "-> {} @ {}", self.dst, self.interval)?;
+        if let Some(duration) = self.duration {
+            write!(f, " d={}", duration)?;
+        }
+        if self.weight != DEFAULT_WEIGHT {
+            write!(f, " w={}", self.weight)?;
+        }
+        if !self.properties.is_empty() {
+            let props: Vec<String> = self.properties.iter().map(|(k, v)| format!("{}: {}", k, v)).collect();
+            write!(f, " {{{}}}", props.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Adjacency lists keyed by source node.
+pub type AdjacencyMap<N = NodeId, T = Timestamp> = HashMap<N, Vec<TemporalEdge<N, T>>>;
+
+/// Inserts `edge` into an adjacency list kept sorted by interval start.
+///
+/// Edges arriving in time order are appended directly; out-of-order
+/// edges are placed after any existing edges with the same start.
+pub(crate) fn insert_sorted<N, T: Time>(edges: &mut Vec<TemporalEdge<N, T>>, edge: TemporalEdge<N, T>) {
+    match edges.last() {
+        Some(last) if last.interval.start > edge.interval.start => {
+            let pos = edges.partition_point(|e| e.interval.start <= edge.interval.start);
+            edges.insert(pos, edge);
+        }
+        _ => edges.push(edge),
+    }
+}
+
+/// Returns the prefix of a sorted adjacency list whose edges start
+/// strictly before `timestamp`.
+pub(crate) fn started_before<N, T: Time>(edges: &[TemporalEdge<N, T>], timestamp: T) -> &[TemporalEdge<N, T>] {
+    &edges[..edges.partition_point(|e| e.interval.start < timestamp)]
+}
+
+/// Returns the prefix of a sorted adjacency list whose edges start at
+/// or before `timestamp`.
+pub(crate) fn started_by<N, T: Time>(edges: &[TemporalEdge<N, T>], timestamp: T) -> &[TemporalEdge<N, T>] {
+    &edges[..edges.partition_point(|e| e.interval.start <= timestamp)]
+}
+
+/// Builds the reverse of `edges`: for every edge `src -> dst`, the list
+/// under `dst` holds an edge whose `dst` field is `src`, built by
+/// [`TemporalEdge::reversed`].
+pub(crate) fn reverse_adjacency<N: NodeKey, T: Time>(edges: &AdjacencyMap<N, T>) -> AdjacencyMap<N, T> {
+    let mut reversed: AdjacencyMap<N, T> = HashMap::new();
+    for (src, neighbors) in edges {
+        for edge in neighbors {
+            reversed
+                .entry(edge.dst.clone())
+                .or_default()
+                .push(edge.reversed(src.clone()));
+        }
+    }
+    for sources in reversed.values_mut() {
+        sources.sort_by_key(|edge| edge.interval.start);
+    }
+    reversed
+}
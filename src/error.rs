@@ -0,0 +1,149 @@
+//! src/error.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module defines [`GraphError`], the error returned by every
+//! graph mutation in [`sequential`](crate::sequential) and
+//! [`chrono`](crate::chrono), [`PersistError`], returned when saving
+//! or loading a graph with [`persist`](crate::persist), and [`WalError`],
+//! returned by the mutations of a [`DurableGraph`](crate::wal::DurableGraph).
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use crate::types::{NodeId, NodeKey, Time, Timestamp};
+
+/// The reasons a graph mutation can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError<N = NodeId, T = Timestamp> {
+    /// The source node of an edge has not been added to the graph.
+    MissingSource(N),
+    /// The destination node of an edge has not been added to the graph.
+    MissingDestination(N),
+    /// The node to remove does not exist.
+    NodeNotFound(N),
+    /// No edge from `src` to `dst` starts at `timestamp`.
+    EdgeNotFound {
+        src: N,
+        dst: N,
+        timestamp: T,
+    },
+    /// The edge interval does not end after it starts.
+    InvalidInterval { start: T, end: T },
+    /// The edge traversal duration is negative.
+    InvalidDuration(T),
+}
+
+impl<N: NodeKey, T: Time> fmt::Display for GraphError<N, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::MissingSource(node) => write!(f, "source node {} does not exist", node),
+            GraphError::MissingDestination(node) => write!(f, "destination node {} does not exist", node),
+            GraphError::NodeNotFound(node) => write!(f, "node {} does not exist", node),
+            GraphError::EdgeNotFound { src, dst, timestamp } => {
+                write!(f, "no edge {} -> {} starts at {}", src, dst, timestamp)
+            }
+            GraphError::InvalidInterval { start, end } => {
+                write!(f, "edge interval [{}, {}) must end after it starts", start, end)
+            }
+            GraphError::InvalidDuration(duration) => write!(f, "edge duration {} must not be negative", duration),
+        }
+    }
+}
+
+impl<N: NodeKey, T: Time> Error for GraphError<N, T> {}
+
+/// The reasons saving or loading a graph can fail.
+#[derive(Debug)]
+pub enum PersistError {
+    /// Reading or writing the underlying stream failed.
+    Io(io::Error),
+    /// The data does not start with the graph file signature.
+    BadMagic,
+    /// The file was written in a major format version this build cannot read.
+    UnsupportedVersion { major: u8, minor: u8 },
+    /// The checksum stored in the file does not match its contents.
+    ChecksumMismatch { stored: u32, computed: u32 },
+    /// The file passed its checksum but its contents are malformed.
+    Corrupt(String),
+}
+
+impl fmt::Display for PersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersistError::Io(err) => write!(f, "i/o error: {}", err),
+            PersistError::BadMagic => write!(f, "not a chronograph file"),
+            PersistError::UnsupportedVersion { major, minor } => {
+                write!(f, "unsupported format version {}.{}", major, minor)
+            }
+            PersistError::ChecksumMismatch { stored, computed } => {
+                write!(f, "checksum mismatch: stored {:08x}, computed {:08x}", stored, computed)
+            }
+            PersistError::Corrupt(reason) => write!(f, "corrupted graph file: {}", reason),
+        }
+    }
+}
+
+impl Error for PersistError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PersistError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PersistError {
+    fn from(err: io::Error) -> Self {
+        PersistError::Io(err)
+    }
+}
+
+/// The reasons a logged mutation can fail.
+#[derive(Debug)]
+pub enum WalError<N = NodeId, T = Timestamp> {
+    /// The graph rejected the mutation. It was still logged, and is
+    /// rejected again when the log is replayed.
+    Graph(GraphError<N, T>),
+    /// Writing the log or a checkpoint failed.
+    Persist(PersistError),
+}
+
+impl<N: NodeKey, T: Time> fmt::Display for WalError<N, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WalError::Graph(err) => write!(f, "{}", err),
+            WalError::Persist(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl<N: NodeKey, T: Time> Error for WalError<N, T> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WalError::Graph(_) => None,
+            WalError::Persist(err) => Some(err),
+        }
+    }
+}
+
+impl<N, T> From<GraphError<N, T>> for WalError<N, T> {
+    fn from(err: GraphError<N, T>) -> Self {
+        WalError::Graph(err)
+    }
+}
+
+impl<N, T> From<PersistError> for WalError<N, T> {
+    fn from(err: PersistError) -> Self {
+        WalError::Persist(err)
+    }
+}
+
+impl<N, T> From<io::Error> for WalError<N, T> {
+    fn from(err: io::Error) -> Self {
+        WalError::Persist(PersistError::Io(err))
+    }
+}
@@ -0,0 +1,526 @@
+//! src/events.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module reads and writes graphs as JSON Lines event streams, one
+//! JSON object per line, each describing a single mutation:
+//!
+//! ```text
+//! {"op":"add_node","node":1}
+//! {"op":"add_node","node":4,"ts":10}
+//! {"op":"add_edge","src":1,"dst":2,"ts":3,"end":9,"weight":2.5,"properties":{"kind":"call"}}
+//! {"op":"add_edge","src":2,"dst":4,"ts":6,"duration":3}
+//! {"op":"remove_edge","src":1,"dst":2,"ts":3}
+//! {"op":"remove_node","node":4,"ts":30}
+//! ```
+//!
+//! Node keys and timestamps may be JSON numbers or strings; either way
+//! their text is parsed with `FromStr`. A `remove_node` without `ts`
+//! erases the node from all of history. Other fields are ignored.
+//!
+//! [`write_events`] writes the stream that rebuilds a graph, so reading
+//! it back into an empty graph of the same directionality gives an
+//! identical graph.
+
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+use crate::edge::{Interval, Properties, PropertyValue, DEFAULT_WEIGHT};
+use crate::import::{ImportError, LineError, OnError};
+use crate::ops::TemporalGraphOps;
+use crate::types::TimeSpan;
+
+/// A summary of a finished event stream.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventReport {
+    /// Lines read, including blank lines.
+    pub lines: usize,
+    /// Events applied to the graph.
+    pub applied: usize,
+    /// Lines skipped under [`OnError::Skip`], in input order.
+    pub skipped: Vec<LineError>,
+}
+
+/// Applies every event of `reader` to `graph`, in order. A line fails if
+/// it is not a valid event or if the graph rejects the mutation.
+pub fn read_events<G, R>(graph: &mut G, reader: R, on_error: OnError) -> Result<EventReport, ImportError>
+where
+    G: TemporalGraphOps,
+    G::Node: FromStr,
+    G::Time: FromStr + TimeSpan,
+    R: BufRead,
+{
+    let mut report = EventReport::default();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        report.lines += 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match apply_event(graph, &line) {
+            Ok(()) => report.applied += 1,
+            Err(message) => {
+                let err = LineError { line: i + 1, message };
+                match on_error {
+                    OnError::Fail => return Err(ImportError::Malformed(err)),
+                    OnError::Skip => report.skipped.push(err),
+                }
+            }
+        }
+    }
+    Ok(report)
+}
+
+fn apply_event<G>(graph: &mut G, line: &str) -> Result<(), String>
+where
+    G: TemporalGraphOps,
+    G::Node: FromStr,
+    G::Time: FromStr + TimeSpan,
+{
+    let Json::Object(fields) = parse_json(line)? else {
+        return Err("expected a JSON object".to_string());
+    };
+    let event = Event { fields };
+    match event.op()? {
+        "add_node" => {
+            let node = event.required("node")?;
+            match event.optional("ts")? {
+                Some(timestamp) => graph.add_node_at(node, timestamp),
+                None => graph.add_node(node),
+            }
+        }
+        "remove_node" => {
+            let node = event.required("node")?;
+            match event.optional("ts")? {
+                Some(timestamp) => graph.remove_node_at(node, timestamp),
+                None => graph.remove_node(node),
+            }
+            .map_err(|err| err.to_string())?;
+        }
+        "add_edge" => {
+            let src = event.required("src")?;
+            let dst = event.required("dst")?;
+            let interval = Interval {
+                start: event.required("ts")?,
+                end: event.optional("end")?,
+            };
+            let weight: Option<f64> = event.optional("weight")?;
+            let properties = event.properties()?;
+            match event.optional("duration")? {
+                Some(_) if weight.is_some() || !properties.is_empty() => {
+                    return Err("an edge with a duration cannot carry a weight or properties".to_string());
+                }
+                Some(duration) => graph.add_edge_with_duration(src, dst, interval, duration),
+                None => graph.add_edge_with(src, dst, interval, weight.unwrap_or(DEFAULT_WEIGHT), properties),
+            }
+            .map_err(|err| err.to_string())?;
+        }
+        "remove_edge" => {
+            graph
+                .remove_edge(event.required("src")?, event.required("dst")?, event.required("ts")?)
+                .map_err(|err| err.to_string())?;
+        }
+        op => return Err(format!("unknown op {:?}", op)),
+    }
+    Ok(())
+}
+
+/// The fields of one event.
+struct Event {
+    fields: Vec<(String, Json)>,
+}
+
+impl Event {
+    /// Returns the value of field `name`, treating `null` as missing.
+    fn get(&self, name: &str) -> Option<&Json> {
+        self.fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+            .filter(|value| !matches!(value, Json::Null))
+    }
+
+    fn op(&self) -> Result<&str, String> {
+        match self.get("op") {
+            Some(Json::Str(op)) => Ok(op),
+            Some(_) => Err("\"op\" must be a string".to_string()),
+            None => Err("missing \"op\"".to_string()),
+        }
+    }
+
+    fn optional<V: FromStr>(&self, name: &str) -> Result<Option<V>, String> {
+        match self.get(name) {
+            Some(Json::Number(text) | Json::Str(text)) => text
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("invalid {:?} value {:?}", name, text)),
+            Some(_) => Err(format!("{:?} must be a number or a string", name)),
+            None => Ok(None),
+        }
+    }
+
+    fn required<V: FromStr>(&self, name: &str) -> Result<V, String> {
+        self.optional(name)?.ok_or_else(|| format!("missing {:?}", name))
+    }
+
+    fn properties(&self) -> Result<Properties, String> {
+        let mut properties = Properties::new();
+        match self.get("properties") {
+            Some(Json::Object(entries)) => {
+                for (key, value) in entries {
+                    let value = match value {
+                        Json::Null => continue,
+                        Json::Bool(flag) => PropertyValue::Bool(*flag),
+                        Json::Str(text) => PropertyValue::Str(text.clone()),
+                        Json::Number(text) => match text.parse::<i64>() {
+                            Ok(int) => PropertyValue::Int(int),
+                            Err(_) => PropertyValue::Float(text.parse().map_err(|_| format!("invalid number {}", text))?),
+                        },
+                        Json::Array(_) | Json::Object(_) => {
+                            return Err(format!("property {:?} must be a string, number or boolean", key));
+                        }
+                    };
+                    properties.insert(key.clone(), value);
+                }
+            }
+            Some(_) => return Err("\"properties\" must be an object".to_string()),
+            None => {}
+        }
+        Ok(properties)
+    }
+}
+
+/// Writes the events that rebuild `graph`: every node, then every edge,
+/// then the end of every node lifetime. Nodes are written in the order of
+/// their text and edges in the order they are stored; an undirected edge
+/// is written once.
+pub fn write_events<G: TemporalGraphOps, W: Write>(graph: &G, mut writer: W) -> io::Result<()> {
+    let mut nodes: Vec<(String, &G::Node)> = graph.get_nodes().iter().map(|node| (node.to_string(), node)).collect();
+    nodes.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (id, node) in &nodes {
+        match graph.node_lifetime(node).and_then(|lifetime| lifetime.born) {
+            Some(born) => writeln!(writer, r#"{{"op":"add_node","node":{},"ts":{}}}"#, json_key(id), json_key(&born.to_string()))?,
+            None => writeln!(writer, r#"{{"op":"add_node","node":{}}}"#, json_key(id))?,
+        }
+    }
+    for (id, node) in &nodes {
+        for edge in graph.edges_of(node).iter().filter(|edge| !edge.is_mirror()) {
+            let mut line = format!(
+                r#"{{"op":"add_edge","src":{},"dst":{},"ts":{}"#,
+                json_key(id),
+                json_key(&edge.dst.to_string()),
+                json_key(&edge.interval.start.to_string())
+            );
+            if let Some(end) = edge.interval.end {
+                line.push_str(&format!(r#","end":{}"#, json_key(&end.to_string())));
+            }
+            if let Some(duration) = edge.duration {
+                line.push_str(&format!(r#","duration":{}"#, json_key(&duration.to_string())));
+            }
+            if edge.weight != DEFAULT_WEIGHT {
+                line.push_str(&format!(r#","weight":{}"#, json_float(edge.weight)));
+            }
+            if !edge.properties.is_empty() {
+                let entries: Vec<String> = edge
+                    .properties
+                    .iter()
+                    .map(|(key, value)| format!("{}:{}", json_string(key), json_property(value)))
+                    .collect();
+                line.push_str(&format!(r#","properties":{{{}}}"#, entries.join(",")));
+            }
+            writeln!(writer, "{}}}", line)?;
+        }
+    }
+    for (id, node) in &nodes {
+        if let Some(died) = graph.node_lifetime(node).and_then(|lifetime| lifetime.died) {
+            writeln!(writer, r#"{{"op":"remove_node","node":{},"ts":{}}}"#, json_key(id), json_key(&died.to_string()))?;
+        }
+    }
+    writer.flush()
+}
+
+/// Writes a node key or timestamp as a JSON number if its text is one,
+/// and as a string otherwise.
+fn json_key(text: &str) -> String {
+    let mut parser = JsonParser { text, pos: 0, depth: 0 };
+    if text.starts_with(|c: char| c == '-' || c.is_ascii_digit()) && parser.number().is_ok() && parser.pos == text.len() {
+        text.to_string()
+    } else {
+        json_string(text)
+    }
+}
+
+/// Writes `value` so that it reads back as a float; JSON has no
+/// infinities or NaN, so those become `null`.
+fn json_float(value: f64) -> String {
+    if value.is_finite() {
+        format!("{:?}", value)
+    } else {
+        "null".to_string()
+    }
+}
+
+fn json_property(value: &PropertyValue) -> String {
+    match value {
+        PropertyValue::Str(text) => json_string(text),
+        PropertyValue::Int(int) => int.to_string(),
+        PropertyValue::Float(float) => json_float(*float),
+        PropertyValue::Bool(flag) => flag.to_string(),
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => {
+                out.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// A parsed JSON value. Numbers keep their text so that keys and
+/// timestamps can be parsed as whatever type the graph uses.
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(String),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+fn parse_json(text: &str) -> Result<Json, String> {
+    let mut parser = JsonParser { text, pos: 0, depth: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos < text.len() {
+        return Err(parser.error("unexpected text after the value"));
+    }
+    Ok(value)
+}
+
+/// How deeply arrays and objects may nest, so that hostile input fails
+/// with an error instead of overflowing the stack.
+const MAX_DEPTH: usize = 128;
+
+/// A recursive-descent parser over one line of JSON.
+struct JsonParser<'a> {
+    text: &'a str,
+    pos: usize,
+    /// Arrays and objects currently open.
+    depth: usize,
+}
+
+impl JsonParser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("{} at column {}", message, self.pos + 1)
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{' | b'[') if self.depth == MAX_DEPTH => Err(self.error("nesting too deep")),
+            Some(b'{') => self.nested(Self::object),
+            Some(b'[') => self.nested(Self::array),
+            Some(b'"') => self.string().map(Json::Str),
+            Some(b't') => self.literal("true", Json::Bool(true)),
+            Some(b'f') => self.literal("false", Json::Bool(false)),
+            Some(b'n') => self.literal("null", Json::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of line")),
+        }
+    }
+
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<Json, String>) -> Result<Json, String> {
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        self.pos += 1;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(self.error("expected a field name"));
+            }
+            let key = self.string()?;
+            self.skip_whitespace();
+            if self.peek() != Some(b':') {
+                return Err(self.error("expected ':'"));
+            }
+            self.pos += 1;
+            fields.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(fields));
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, String> {
+        self.pos += 1;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Json::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(values));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Json) -> Result<Json, String> {
+        if self.text[self.pos..].starts_with(word) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            Err(self.error("invalid literal"))
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.pos;
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => self.digits(),
+            _ => return Err(self.error("invalid number")),
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if !self.peek().is_some_and(|b| b.is_ascii_digit()) {
+                return Err(self.error("invalid number"));
+            }
+            self.digits();
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !self.peek().is_some_and(|b| b.is_ascii_digit()) {
+                return Err(self.error("invalid number"));
+            }
+            self.digits();
+        }
+        Ok(Json::Number(self.text[start..self.pos].to_string()))
+    }
+
+    fn digits(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let Some(c) = self.text[self.pos..].chars().next() else {
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(out),
+                '\\' => {
+                    let Some(escape) = self.text[self.pos..].chars().next() else {
+                        return Err(self.error("unterminated string"));
+                    };
+                    self.pos += escape.len_utf8();
+                    match escape {
+                        '"' | '\\' | '/' => out.push(escape),
+                        'b' => out.push('\u{8}'),
+                        'f' => out.push('\u{c}'),
+                        'n' => out.push('\n'),
+                        'r' => out.push('\r'),
+                        't' => out.push('\t'),
+                        'u' => out.push(self.unicode_escape()?),
+                        _ => return Err(self.error("invalid escape")),
+                    }
+                }
+                c if c < ' ' => return Err(self.error("control character in string")),
+                c => out.push(c),
+            }
+        }
+    }
+
+    /// Reads the hex digits of a `\u` escape, joining surrogate pairs.
+    fn unicode_escape(&mut self) -> Result<char, String> {
+        let high = self.hex4()?;
+        let code = if (0xd800..0xdc00).contains(&high) {
+            if !self.text[self.pos..].starts_with("\\u") {
+                return Err(self.error("unpaired surrogate"));
+            }
+            self.pos += 2;
+            let low = self.hex4()?;
+            if !(0xdc00..0xe000).contains(&low) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let hex = self
+            .text
+            .get(self.pos..self.pos + 4)
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(u32::from_str_radix(hex, 16).expect("four hex digits"))
+    }
+}
@@ -0,0 +1,357 @@
+//! src/export.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module writes a temporal graph, or the part of it present in a
+//! time window, to formats other tools can read:
+//!
+//! - Graphviz DOT, with intervals, weights and properties as attributes;
+//! - GraphML, with node lifetimes and edge intervals as data keys;
+//! - GEXF in dynamic mode, where node lifetimes and edge intervals become
+//!   spells, so Gephi can animate the network over time.
+//!
+//! Nodes and edges are identified by the text of their keys. Nodes are
+//! written in the order of that text and edges in the order they are
+//! stored, so the same graph always gives the same file on either backend.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::io::{self, Write};
+use crate::edge::{Interval, Lifetime, PropertyValue, TemporalEdge};
+use crate::ops::TemporalGraphOps;
+use crate::types::Time;
+
+/// The nodes and edges to export, in output order.
+struct Selection<'g, N, T> {
+    /// The id of every node, with its lifetime.
+    nodes: Vec<(String, Lifetime<T>)>,
+    /// Each edge as the positions of its endpoints in `nodes`.
+    edges: Vec<(usize, usize, &'g TemporalEdge<N, T>)>,
+}
+
+/// Selects the nodes present in `window` and the edges between them that
+/// are active at some point in it, or everything if there is no window.
+/// Undirected edges are taken once.
+fn select<G: TemporalGraphOps>(graph: &G, window: Option<(G::Time, G::Time)>) -> Selection<'_, G::Node, G::Time> {
+    let mut nodes: Vec<(String, &G::Node, _)> = graph
+        .get_nodes()
+        .iter()
+        .map(|node| (node.to_string(), node, graph.node_lifetime(node).unwrap_or_default()))
+        .filter(|(_, _, lifetime)| window.is_none_or(|(t_start, t_end)| lifetime.overlaps(t_start, t_end)))
+        .collect();
+    nodes.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let index: HashMap<&G::Node, usize> = nodes.iter().enumerate().map(|(i, &(_, node, _))| (node, i)).collect();
+    let mut edges = Vec::new();
+    for (i, &(_, node, _)) in nodes.iter().enumerate() {
+        for edge in graph.edges_of(node) {
+            if edge.is_mirror() || !window.is_none_or(|(t_start, t_end)| edge.interval.overlaps(t_start, t_end)) {
+                continue;
+            }
+            if let Some(&j) = index.get(&edge.dst) {
+                edges.push((i, j, edge));
+            }
+        }
+    }
+    Selection {
+        nodes: nodes.into_iter().map(|(id, _, lifetime)| (id, lifetime)).collect(),
+        edges,
+    }
+}
+
+/// Writes `graph` in Graphviz DOT. Node lifetimes and edge intervals,
+/// weights, durations and properties are written as attributes, and each
+/// edge is labelled with its interval.
+pub fn write_dot<G: TemporalGraphOps, W: Write>(graph: &G, mut writer: W, window: Option<(G::Time, G::Time)>) -> io::Result<()> {
+    let selection = select(graph, window);
+    let (kind, arrow) = if graph.is_directed() { ("digraph", "->") } else { ("graph", "--") };
+    writeln!(writer, "{} {{", kind)?;
+    for (id, lifetime) in &selection.nodes {
+        write!(writer, "  {}", dot_quote(id))?;
+        let mut attributes = Vec::new();
+        if let Some(born) = lifetime.born {
+            attributes.push(("born".to_string(), born.to_string()));
+        }
+        if let Some(died) = lifetime.died {
+            attributes.push(("died".to_string(), died.to_string()));
+        }
+        write_dot_attributes(&mut writer, &attributes)?;
+    }
+    for &(i, j, edge) in &selection.edges {
+        write!(writer, "  {} {} {}", dot_quote(&selection.nodes[i].0), arrow, dot_quote(&selection.nodes[j].0))?;
+        let mut attributes = vec![
+            ("label".to_string(), edge.interval.to_string()),
+            ("start".to_string(), edge.interval.start.to_string()),
+        ];
+        if let Some(end) = edge.interval.end {
+            attributes.push(("end".to_string(), end.to_string()));
+        }
+        attributes.push(("weight".to_string(), edge.weight.to_string()));
+        if let Some(duration) = edge.duration {
+            attributes.push(("duration".to_string(), duration.to_string()));
+        }
+        attributes.extend(edge.properties.iter().map(|(key, value)| (key.clone(), property_text(value))));
+        write_dot_attributes(&mut writer, &attributes)?;
+    }
+    writeln!(writer, "}}")?;
+    writer.flush()
+}
+
+fn write_dot_attributes<W: Write>(writer: &mut W, attributes: &[(String, String)]) -> io::Result<()> {
+    if !attributes.is_empty() {
+        let list: Vec<String> = attributes
+            .iter()
+            .map(|(key, value)| format!("{}={}", dot_quote(key), dot_quote(value)))
+            .collect();
+        write!(writer, " [{}]", list.join(", "))?;
+    }
+    writeln!(writer, ";")
+}
+
+/// Writes `graph` in GraphML. Every edge becomes its own `<edge>`, with
+/// `start`, `end`, `weight`, `duration` and each property as data; nodes
+/// carry their `born` and `died` times.
+pub fn write_graphml<G: TemporalGraphOps, W: Write>(graph: &G, mut writer: W, window: Option<(G::Time, G::Time)>) -> io::Result<()> {
+    let selection = select(graph, window);
+    let time_type = time_type(&selection);
+    let properties = property_types(&selection);
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+    for key in ["born", "died"] {
+        writeln!(writer, r#"  <key id="{0}" for="node" attr.name="{0}" attr.type="{1}"/>"#, key, time_type)?;
+    }
+    for (key, kind) in [("start", time_type), ("end", time_type), ("weight", "double"), ("duration", time_type)] {
+        writeln!(writer, r#"  <key id="{0}" for="edge" attr.name="{0}" attr.type="{1}"/>"#, key, kind)?;
+    }
+    for (i, (name, kind)) in properties.iter().enumerate() {
+        writeln!(writer, r#"  <key id="p{}" for="edge" attr.name="{}" attr.type="{}"/>"#, i, xml_escape(name), kind.name())?;
+    }
+    let direction = if graph.is_directed() { "directed" } else { "undirected" };
+    writeln!(writer, r#"  <graph id="G" edgedefault="{}">"#, direction)?;
+
+    for (id, lifetime) in &selection.nodes {
+        write!(writer, r#"    <node id="{}">"#, xml_escape(id))?;
+        write_data(&mut writer, "born", lifetime.born)?;
+        write_data(&mut writer, "died", lifetime.died)?;
+        writeln!(writer, "</node>")?;
+    }
+    for (e, &(i, j, edge)) in selection.edges.iter().enumerate() {
+        write!(
+            writer,
+            r#"    <edge id="e{}" source="{}" target="{}">"#,
+            e,
+            xml_escape(&selection.nodes[i].0),
+            xml_escape(&selection.nodes[j].0)
+        )?;
+        write_data(&mut writer, "start", Some(edge.interval.start))?;
+        write_data(&mut writer, "end", edge.interval.end)?;
+        write_data(&mut writer, "weight", Some(edge.weight))?;
+        write_data(&mut writer, "duration", edge.duration)?;
+        for (i, name) in properties.keys().enumerate() {
+            if let Some(value) = edge.properties.get(name) {
+                write_data(&mut writer, &format!("p{}", i), Some(property_text(value)))?;
+            }
+        }
+        writeln!(writer, "</edge>")?;
+    }
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</graphml>")?;
+    writer.flush()
+}
+
+fn write_data<W: Write, V: Display>(writer: &mut W, key: &str, value: Option<V>) -> io::Result<()> {
+    match value {
+        Some(value) => write!(writer, r#"<data key="{}">{}</data>"#, key, xml_escape(&value.to_string())),
+        None => Ok(()),
+    }
+}
+
+/// Writes `graph` in GEXF 1.2 dynamic mode. Node lifetimes become node
+/// spells, and edges from one node to another are merged into a single
+/// `<edge>` with one spell per interval; their weights and properties
+/// are written as dynamic attribute values over the same spells. An
+/// attribute holds one value at a time, so an edge whose interval
+/// overlaps those of every merged `<edge>` so far starts another one.
+/// Intervals are half-open, so bounded ones end with `endopen`.
+pub fn write_gexf<G: TemporalGraphOps, W: Write>(graph: &G, mut writer: W, window: Option<(G::Time, G::Time)>) -> io::Result<()> {
+    let selection = select(graph, window);
+    let properties = property_types(&selection);
+    // Edges come in order of start, so each joins the first run of
+    // parallel edges that has ended by then.
+    let mut merged: BTreeMap<(usize, usize), Vec<Vec<_>>> = BTreeMap::new();
+    for &(i, j, edge) in &selection.edges {
+        let runs = merged.entry((i, j)).or_default();
+        let ended = |run: &&mut Vec<&TemporalEdge<_, _>>| run.last().and_then(|last| last.interval.end).is_some_and(|end| end <= edge.interval.start);
+        match runs.iter_mut().find(ended) {
+            Some(run) => run.push(edge),
+            None => runs.push(vec![edge]),
+        }
+    }
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<gexf xmlns="http://www.gexf.net/1.2draft" version="1.2">"#)?;
+    let direction = if graph.is_directed() { "directed" } else { "undirected" };
+    writeln!(writer, r#"  <graph mode="dynamic" defaultedgetype="{}" timerepresentation="interval">"#, direction)?;
+    writeln!(writer, r#"    <attributes class="edge" mode="dynamic">"#)?;
+    writeln!(writer, r#"      <attribute id="weight" title="weight" type="double"/>"#)?;
+    for (i, (name, kind)) in properties.iter().enumerate() {
+        writeln!(writer, r#"      <attribute id="p{}" title="{}" type="{}"/>"#, i, xml_escape(name), kind.name())?;
+    }
+    writeln!(writer, "    </attributes>")?;
+
+    writeln!(writer, "    <nodes>")?;
+    for (id, lifetime) in &selection.nodes {
+        let id = xml_escape(id);
+        let mut spell = String::new();
+        if let Some(born) = lifetime.born {
+            spell.push_str(&format!(r#" start="{}""#, born));
+        }
+        if let Some(died) = lifetime.died {
+            spell.push_str(&format!(r#" endopen="{}""#, died));
+        }
+        writeln!(writer, r#"      <node id="{0}" label="{0}"{1}/>"#, id, spell)?;
+    }
+    writeln!(writer, "    </nodes>")?;
+
+    writeln!(writer, "    <edges>")?;
+    let runs = merged.iter().flat_map(|(&pair, runs)| runs.iter().map(move |run| (pair, run)));
+    for (e, ((i, j), edges)) in runs.enumerate() {
+        writeln!(
+            writer,
+            r#"      <edge id="e{}" source="{}" target="{}">"#,
+            e,
+            xml_escape(&selection.nodes[i].0),
+            xml_escape(&selection.nodes[j].0)
+        )?;
+        writeln!(writer, "        <attvalues>")?;
+        for edge in edges {
+            let spell = gexf_spell(&edge.interval);
+            writeln!(writer, r#"          <attvalue for="weight" value="{}"{}/>"#, edge.weight, spell)?;
+            for (p, name) in properties.keys().enumerate() {
+                if let Some(value) = edge.properties.get(name) {
+                    writeln!(writer, r#"          <attvalue for="p{}" value="{}"{}/>"#, p, xml_escape(&property_text(value)), spell)?;
+                }
+            }
+        }
+        writeln!(writer, "        </attvalues>")?;
+        writeln!(writer, "        <spells>")?;
+        for edge in edges {
+            writeln!(writer, "          <spell{}/>", gexf_spell(&edge.interval))?;
+        }
+        writeln!(writer, "        </spells>")?;
+        writeln!(writer, "      </edge>")?;
+    }
+    writeln!(writer, "    </edges>")?;
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</gexf>")?;
+    writer.flush()
+}
+
+fn gexf_spell<T: Display>(interval: &Interval<T>) -> String {
+    match &interval.end {
+        Some(end) => format!(r#" start="{}" endopen="{}""#, interval.start, end),
+        None => format!(r#" start="{}""#, interval.start),
+    }
+}
+
+/// The type of a property column, widened as more values are seen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueType {
+    Bool,
+    Int,
+    Float,
+    Str,
+}
+
+impl ValueType {
+    fn of(value: &PropertyValue) -> Self {
+        match value {
+            PropertyValue::Bool(_) => ValueType::Bool,
+            PropertyValue::Int(_) => ValueType::Int,
+            PropertyValue::Float(_) => ValueType::Float,
+            PropertyValue::Str(_) => ValueType::Str,
+        }
+    }
+
+    fn widen(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (ValueType::Int, ValueType::Float) | (ValueType::Float, ValueType::Int) => ValueType::Float,
+            _ => ValueType::Str,
+        }
+    }
+
+    /// Returns the type's name, which GraphML and GEXF share.
+    fn name(self) -> &'static str {
+        match self {
+            ValueType::Bool => "boolean",
+            ValueType::Int => "long",
+            ValueType::Float => "double",
+            ValueType::Str => "string",
+        }
+    }
+}
+
+/// Returns every property name used by the selected edges with the
+/// narrowest type that fits all of its values.
+fn property_types<N, T>(selection: &Selection<'_, N, T>) -> BTreeMap<String, ValueType> {
+    let mut types: BTreeMap<String, ValueType> = BTreeMap::new();
+    for (_, _, edge) in &selection.edges {
+        for (name, value) in &edge.properties {
+            let kind = ValueType::of(value);
+            types
+                .entry(name.clone())
+                .and_modify(|seen| *seen = seen.widen(kind))
+                .or_insert(kind);
+        }
+    }
+    types
+}
+
+/// Returns the GraphML type of the selected timestamps: `long` if they
+/// all read as integers, `double` if they all read as numbers, and
+/// `string` otherwise.
+fn time_type<N, T: Time>(selection: &Selection<'_, N, T>) -> &'static str {
+    let lifetimes = selection.nodes.iter().flat_map(|(_, lifetime)| [lifetime.born, lifetime.died]);
+    let intervals = selection.edges.iter().flat_map(|(_, _, edge)| [Some(edge.interval.start), edge.interval.end, edge.duration]);
+    let texts: Vec<String> = lifetimes.chain(intervals).flatten().map(|t| t.to_string()).collect();
+    if texts.iter().all(|text| text.parse::<i64>().is_ok()) {
+        "long"
+    } else if texts.iter().all(|text| text.parse::<f64>().is_ok()) {
+        "double"
+    } else {
+        "string"
+    }
+}
+
+/// Returns a property value as plain text, without the quotes its
+/// `Display` puts around strings.
+fn property_text(value: &PropertyValue) -> String {
+    match value {
+        PropertyValue::Str(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn dot_quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,443 @@
+//! src/import.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module implements a streaming importer for temporal edge lists:
+//! text files with one `src dst timestamp [extra columns]` edge per line,
+//! such as the SNAP temporal datasets, CSV or TSV exports.
+//!
+//! Input is read in chunks of lines, so files larger than memory can be
+//! imported. Nodes are created on first use and exist at all times.
+//! Fields are split on the delimiter without any quoting rules.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+use crate::edge::{Interval, Properties, PropertyValue, DEFAULT_WEIGHT};
+use crate::ops::TemporalGraphOps;
+
+/// How the fields of a line are separated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    /// Any run of spaces and tabs, as in SNAP files.
+    Whitespace,
+    /// A single character, such as `,` or `\t`; fields are trimmed.
+    Char(char),
+}
+
+/// A column picked by position, counting from `0`, or by header name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Column {
+    Index(usize),
+    Name(String),
+}
+
+impl From<usize> for Column {
+    fn from(index: usize) -> Self {
+        Column::Index(index)
+    }
+}
+
+impl From<&str> for Column {
+    fn from(name: &str) -> Self {
+        Column::Name(name.to_string())
+    }
+}
+
+/// What to do with a line that cannot be imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnError {
+    /// Stop and return the error.
+    #[default]
+    Fail,
+    /// Record the error in the [`ImportReport`] and go on.
+    Skip,
+}
+
+/// Settings for [`import_edges`].
+///
+/// By default the source, destination and timestamp are columns `0`, `1`
+/// and `2`, lines starting with `#` are comments, there is no header and
+/// every edge is open-ended.
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    delimiter: Delimiter,
+    comment_prefix: Option<String>,
+    has_header: bool,
+    src: Column,
+    dst: Column,
+    time: Column,
+    end: Option<Column>,
+    weight: Option<Column>,
+    properties: Vec<(Column, String)>,
+    on_error: OnError,
+    chunk_size: usize,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self::snap()
+    }
+}
+
+impl ImportOptions {
+    pub fn new(delimiter: Delimiter) -> Self {
+        Self {
+            delimiter,
+            comment_prefix: Some("#".to_string()),
+            has_header: false,
+            src: Column::Index(0),
+            dst: Column::Index(1),
+            time: Column::Index(2),
+            end: None,
+            weight: None,
+            properties: Vec::new(),
+            on_error: OnError::Fail,
+            chunk_size: 1 << 16,
+        }
+    }
+
+    /// Whitespace-separated fields, as in SNAP temporal networks.
+    pub fn snap() -> Self {
+        Self::new(Delimiter::Whitespace)
+    }
+
+    pub fn csv() -> Self {
+        Self::new(Delimiter::Char(','))
+    }
+
+    pub fn tsv() -> Self {
+        Self::new(Delimiter::Char('\t'))
+    }
+
+    /// Treats the first line that is not blank or a comment as a header,
+    /// whose names can then select columns.
+    pub fn with_header(mut self) -> Self {
+        self.has_header = true;
+        self
+    }
+
+    /// Sets the prefix marking comment lines, or `None` for no comments.
+    pub fn with_comment_prefix(mut self, prefix: Option<&str>) -> Self {
+        self.comment_prefix = prefix.map(str::to_string);
+        self
+    }
+
+    pub fn with_columns(mut self, src: impl Into<Column>, dst: impl Into<Column>, time: impl Into<Column>) -> Self {
+        self.src = src.into();
+        self.dst = dst.into();
+        self.time = time.into();
+        self
+    }
+
+    /// Reads the end of each edge's interval from `column`, making the
+    /// edges bounded.
+    pub fn with_end_column(mut self, column: impl Into<Column>) -> Self {
+        self.end = Some(column.into());
+        self
+    }
+
+    pub fn with_weight_column(mut self, column: impl Into<Column>) -> Self {
+        self.weight = Some(column.into());
+        self
+    }
+
+    /// Stores `column` as the edge property `name`. Values are read as
+    /// integers, floats or booleans when they parse as one, and as
+    /// strings otherwise; empty fields are left out.
+    pub fn with_property(mut self, column: impl Into<Column>, name: impl Into<String>) -> Self {
+        self.properties.push((column.into(), name.into()));
+        self
+    }
+
+    pub fn on_error(mut self, on_error: OnError) -> Self {
+        self.on_error = on_error;
+        self
+    }
+
+    /// Sets how many lines are read, and parsed, at a time.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Returns `true` for blank and comment lines.
+    fn skips(&self, line: &str) -> bool {
+        let line = line.trim();
+        line.is_empty() || self.comment_prefix.as_deref().is_some_and(|prefix| line.starts_with(prefix))
+    }
+
+    fn fields<'l>(&self, line: &'l str) -> Vec<&'l str> {
+        match self.delimiter {
+            Delimiter::Whitespace => line.split_whitespace().collect(),
+            Delimiter::Char(c) => line.split(c).map(str::trim).collect(),
+        }
+    }
+}
+
+/// A line that could not be imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    /// The line number, counting from `1`.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// The reasons an import can stop.
+#[derive(Debug)]
+pub enum ImportError {
+    /// Reading the input failed.
+    Io(io::Error),
+    /// A column selected by name is not in the header, or the input has
+    /// no header to select it from.
+    UnknownColumn(String),
+    /// A line could not be imported and errors are not skipped.
+    Malformed(LineError),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Io(err) => write!(f, "i/o error: {}", err),
+            ImportError::UnknownColumn(name) => write!(f, "no column named {:?} in the header", name),
+            ImportError::Malformed(err) => write!(f, "malformed input at {}", err),
+        }
+    }
+}
+
+impl Error for ImportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ImportError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ImportError {
+    fn from(err: io::Error) -> Self {
+        ImportError::Io(err)
+    }
+}
+
+/// A summary of a finished import.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Lines read, including headers, comments and blank lines.
+    pub lines: usize,
+    pub edges_added: usize,
+    /// Nodes created because an edge referred to them.
+    pub nodes_added: usize,
+    /// Lines skipped under [`OnError::Skip`], in input order.
+    pub skipped: Vec<LineError>,
+}
+
+/// An edge parsed from one line.
+pub(crate) struct ParsedEdge<N, T> {
+    line: usize,
+    src: N,
+    dst: N,
+    interval: Interval<T>,
+    weight: f64,
+    properties: Properties,
+}
+
+/// The result of parsing one line: `None` for lines without an edge.
+pub(crate) type ParsedLine<N, T> = Result<Option<ParsedEdge<N, T>>, LineError>;
+
+/// Splits and parses lines once the columns have been resolved.
+pub(crate) struct LineParser<'a> {
+    options: &'a ImportOptions,
+    src: usize,
+    dst: usize,
+    time: usize,
+    end: Option<usize>,
+    weight: Option<usize>,
+    properties: Vec<(usize, &'a str)>,
+}
+
+impl<'a> LineParser<'a> {
+    fn new(options: &'a ImportOptions, header: Option<&[&str]>) -> Result<Self, ImportError> {
+        let resolve = |column: &Column| match column {
+            Column::Index(index) => Ok(*index),
+            Column::Name(name) => header
+                .and_then(|header| header.iter().position(|field| field == name))
+                .ok_or_else(|| ImportError::UnknownColumn(name.clone())),
+        };
+        Ok(Self {
+            options,
+            src: resolve(&options.src)?,
+            dst: resolve(&options.dst)?,
+            time: resolve(&options.time)?,
+            end: options.end.as_ref().map(resolve).transpose()?,
+            weight: options.weight.as_ref().map(resolve).transpose()?,
+            properties: options
+                .properties
+                .iter()
+                .map(|(column, name)| Ok((resolve(column)?, name.as_str())))
+                .collect::<Result<_, ImportError>>()?,
+        })
+    }
+
+    pub(crate) fn parse<N: FromStr, T: FromStr + PartialOrd>(&self, line_number: usize, line: &str) -> ParsedLine<N, T> {
+        if self.options.skips(line) {
+            return Ok(None);
+        }
+        let fields = Fields {
+            values: self.options.fields(line),
+            line: line_number,
+        };
+        let src: N = fields.parse(self.src, "source node")?;
+        let dst: N = fields.parse(self.dst, "destination node")?;
+        let start: T = fields.parse(self.time, "timestamp")?;
+        let end: Option<T> = self.end.map(|index| fields.parse(index, "end timestamp")).transpose()?;
+        if end.as_ref().is_some_and(|end| *end <= start) {
+            return Err(fields.error("interval ends at or before its start".to_string()));
+        }
+        let weight = match self.weight {
+            Some(index) => fields.parse(index, "weight")?,
+            None => DEFAULT_WEIGHT,
+        };
+        let mut properties = Properties::new();
+        for &(index, name) in &self.properties {
+            let value = fields.get(index)?;
+            if !value.is_empty() {
+                properties.insert(name.to_string(), property_value(value));
+            }
+        }
+        Ok(Some(ParsedEdge {
+            line: line_number,
+            src,
+            dst,
+            interval: Interval { start, end },
+            weight,
+            properties,
+        }))
+    }
+}
+
+/// The fields of one line.
+struct Fields<'l> {
+    values: Vec<&'l str>,
+    line: usize,
+}
+
+impl<'l> Fields<'l> {
+    fn get(&self, index: usize) -> Result<&'l str, LineError> {
+        self.values
+            .get(index)
+            .copied()
+            .ok_or_else(|| self.error(format!("expected at least {} fields, found {}", index + 1, self.values.len())))
+    }
+
+    fn parse<V: FromStr>(&self, index: usize, what: &str) -> Result<V, LineError> {
+        let value = self.get(index)?;
+        value.parse().map_err(|_| self.error(format!("invalid {} {:?}", what, value)))
+    }
+
+    fn error(&self, message: String) -> LineError {
+        LineError { line: self.line, message }
+    }
+}
+
+fn property_value(value: &str) -> PropertyValue {
+    if let Ok(int) = value.parse::<i64>() {
+        PropertyValue::Int(int)
+    } else if let Ok(float) = value.parse::<f64>() {
+        PropertyValue::Float(float)
+    } else if let Ok(flag) = value.parse::<bool>() {
+        PropertyValue::Bool(flag)
+    } else {
+        PropertyValue::Str(value.to_string())
+    }
+}
+
+/// Imports every edge of `reader` into `graph`, creating missing nodes.
+pub fn import_edges<G, R>(graph: &mut G, reader: R, options: &ImportOptions) -> Result<ImportReport, ImportError>
+where
+    G: TemporalGraphOps,
+    G::Node: FromStr,
+    G::Time: FromStr,
+    R: BufRead,
+{
+    import_with(graph, reader, options, |parser, lines| {
+        lines.iter().map(|(number, line)| parser.parse(*number, line)).collect()
+    })
+}
+
+/// Drives an import, handing each chunk of numbered lines to `parse_chunk`
+/// and adding the results to `graph` in input order.
+pub(crate) fn import_with<G, R, P>(graph: &mut G, reader: R, options: &ImportOptions, parse_chunk: P) -> Result<ImportReport, ImportError>
+where
+    G: TemporalGraphOps,
+    G::Node: FromStr,
+    G::Time: FromStr,
+    R: BufRead,
+    P: Fn(&LineParser<'_>, &[(usize, String)]) -> Vec<ParsedLine<G::Node, G::Time>>,
+{
+    let mut report = ImportReport::default();
+    let mut lines = reader.lines().enumerate().map(|(i, line)| line.map(|line| (i + 1, line)));
+
+    let parser = if options.has_header {
+        let mut header = String::new();
+        for line in lines.by_ref() {
+            let (_, line) = line?;
+            report.lines += 1;
+            if !options.skips(&line) {
+                header = line;
+                break;
+            }
+        }
+        LineParser::new(options, Some(&options.fields(&header)))?
+    } else {
+        LineParser::new(options, None)?
+    };
+
+    let mut chunk = Vec::with_capacity(options.chunk_size.min(1 << 16));
+    loop {
+        chunk.clear();
+        for line in lines.by_ref().take(options.chunk_size) {
+            chunk.push(line?);
+        }
+        if chunk.is_empty() {
+            return Ok(report);
+        }
+        report.lines += chunk.len();
+        for parsed in parse_chunk(&parser, &chunk) {
+            let result = parsed.and_then(|edge| match edge {
+                Some(edge) => add_parsed(graph, edge, &mut report),
+                None => Ok(()),
+            });
+            if let Err(err) = result {
+                match options.on_error {
+                    OnError::Fail => return Err(ImportError::Malformed(err)),
+                    OnError::Skip => report.skipped.push(err),
+                }
+            }
+        }
+    }
+}
+
+fn add_parsed<G: TemporalGraphOps>(graph: &mut G, edge: ParsedEdge<G::Node, G::Time>, report: &mut ImportReport) -> Result<(), LineError> {
+    for node in [&edge.src, &edge.dst] {
+        if !graph.contains_node(node) {
+            graph.add_node(node.clone());
+            report.nodes_added += 1;
+        }
+    }
+    graph
+        .add_edge_with(edge.src, edge.dst, edge.interval, edge.weight, edge.properties)
+        .map_err(|err| LineError { line: edge.line, message: err.to_string() })?;
+    report.edges_added += 1;
+    Ok(())
+}
@@ -1,268 +1,2134 @@
-/// src/lib.rs
-///
-/// # Author
-/// Yifan Jiang, 2025
-/// alvinjiang01@hotmail.com
-/// GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
-
+//! src/lib.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+
+pub mod types;
+pub mod edge;
+pub mod error;
+pub mod ops;
+pub mod store;
 pub mod sequential;
 pub mod chrono;
+pub mod snapshot;
+pub mod paths;
+pub mod reachability;
+pub mod components;
+pub mod motifs;
+pub mod pagerank;
+pub mod centrality;
+pub mod cores;
+pub mod persist;
+pub mod wal;
+pub mod import;
+pub mod export;
+pub mod events;
 
 #[cfg(test)]
-mod tg_unit_tests {
-    use super::sequential::*;
-    use std::time::Instant;
-
-    #[test]
-    fn test_add_node() {
-        let mut tg = TemporalGraph::new();
-        tg.add_node(1);
-        assert!(tg.get_nodes().contains(&1));
-
-        eprintln!("\nTG for test_add_node:");
-        tg.print();
+mod graph_unit_tests {
+    use std::collections::HashSet;
+    use super::edge::*;
+    use super::error::GraphError;
+    use super::ops::TemporalGraphOps;
+    use super::paths::{earliest_arrival, fastest_paths, fewest_hops, latest_departure};
+    use super::reachability::reachable_from;
+    use super::components::{strong_temporal_components, weak_components_at};
+    use super::motifs::{count_motifs, Motif, MotifKind};
+    use super::pagerank::{temporal_pagerank, PageRankConfig, TemporalPageRank};
+    use super::centrality::{temporal_betweenness, temporal_closeness, temporal_closeness_of};
+    use super::cores::{core_evolution, core_numbers, k_core, sliding_windows};
+    use super::types::{NodeId, Timestamp};
+
+    pub fn test_add_node<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node(1);
+        assert!(g.get_nodes().contains(&1));
+
+        eprintln!("\n{} for test_add_node:", std::any::type_name::<G>());
+        g.print();
     }
 
-    #[test]
-    fn test_add_edge_success() {
-        let mut tg = TemporalGraph::new();
-        tg.add_node(1);
-        tg.add_node(2);
-        let result = tg.add_edge(1, 2, 5);
+    pub fn test_add_edge_success<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node(1);
+        g.add_node(2);
+        let result = g.add_edge(1, 2, 5);
         assert!(result.is_ok());
-        assert_eq!(tg.get_edges().get(&1).unwrap(), &vec![(2, 5)]);
+        assert_eq!(g.get_edges().get(&1).unwrap(), &vec![TemporalEdge::new(2, Interval::starting_at(5))]);
 
-        eprintln!("\nTG for test_add_edge_success:");
-        tg.print();
+        eprintln!("\n{} for test_add_edge_success:", std::any::type_name::<G>());
+        g.print();
     }
 
-    #[test]
-    fn test_add_edge_missing_node() {
-        let mut tg = TemporalGraph::new();
-        tg.add_node(1);
-        let result = tg.add_edge(1, 2, 5);
-        assert!(result.is_err());
+    pub fn test_add_edge_missing_node<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node(1);
+        let result = g.add_edge(1, 2, 5);
+        assert_eq!(result, Err(GraphError::MissingDestination(2)));
+        assert_eq!(g.add_edge(3, 1, 5), Err(GraphError::MissingSource(3)));
     }
 
-    #[test]
-    fn test_get_neighbors_at() {
-        let mut tg = TemporalGraph::new();
-        tg.add_node(1);
-        tg.add_node(2);
-        tg.add_node(3);
-        tg.add_edge(1, 2, 5).unwrap();
-        tg.add_edge(1, 3, 15).unwrap();
-        let neighbors_at_10 = tg.get_neighbors_at(1, 10);
+    pub fn test_error_propagation<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        fn build<G: TemporalGraphOps<Node = NodeId, Time = Timestamp>>(g: &mut G) -> Result<(), Box<dyn std::error::Error>> {
+            g.add_node(1);
+            g.add_edge(1, 2, 5)?;
+            Ok(())
+        }
+
+        let mut g = G::default();
+        let err = build(&mut g).unwrap_err();
+        assert_eq!(err.to_string(), "destination node 2 does not exist");
+    }
+
+    pub fn test_get_neighbors_at<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node(1);
+        g.add_node(2);
+        g.add_node(3);
+        g.add_edge(1, 2, 5).unwrap();
+        g.add_edge(1, 3, 15).unwrap();
+        let neighbors_at_10 = g.get_neighbors_at(1, 10);
         assert_eq!(neighbors_at_10, vec![2]);
-        let neighbors_at_20 = tg.get_neighbors_at(1, 20);
+        let neighbors_at_20 = g.get_neighbors_at(1, 20);
         assert_eq!(neighbors_at_20.len(), 2);
         assert!(neighbors_at_20.contains(&2));
         assert!(neighbors_at_20.contains(&3));
 
-        eprintln!("\nTG for test_get_neighbors_at:");
-        tg.print();
+        eprintln!("\n{} for test_get_neighbors_at:", std::any::type_name::<G>());
+        g.print();
     }
 
-    #[test]
-    fn test_get_neighbors_no_edge() {
-        let mut tg = TemporalGraph::new();
-        tg.add_node(1);
-        let neighbors = tg.get_neighbors_at(1, 10);
+    pub fn test_add_edge_interval<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node(1);
+        g.add_node(2);
+        g.add_node(3);
+        g.add_edge_interval(1, 2, 5, 10).unwrap();
+        g.add_edge(1, 3, 8).unwrap();
+        assert!(g.get_neighbors_at(1, 4).is_empty());
+        assert_eq!(g.get_neighbors_at(1, 5), vec![2]);
+        assert_eq!(g.get_neighbors_at(1, 9).len(), 2);
+        assert_eq!(g.get_neighbors_at(1, 10), vec![3]);
+        assert_eq!(g.get_neighbors_at(1, 1_000), vec![3]);
+
+        eprintln!("\n{} for test_add_edge_interval:", std::any::type_name::<G>());
+        g.print();
+    }
+
+    pub fn test_add_edge_interval_invalid<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node(1);
+        g.add_node(2);
+        assert_eq!(g.add_edge_interval(1, 2, 10, 10), Err(GraphError::InvalidInterval { start: 10, end: 10 }));
+        assert_eq!(g.add_edge_interval(1, 2, 10, 5), Err(GraphError::InvalidInterval { start: 10, end: 5 }));
+        assert!(g.get_edges().get(&1).is_none());
+    }
+
+    pub fn test_remove_edge_interval<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node(1);
+        g.add_node(2);
+        g.add_edge_interval(1, 2, 5, 10).unwrap();
+
+        assert_eq!(g.remove_edge(1, 2, 5), Ok((1, 2, 5)));
+        assert!(g.get_neighbors_at(1, 7).is_empty());
+    }
+
+    pub fn test_add_edge_out_of_order<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=5).for_each(|i| g.add_node(i));
+        g.add_edge(1, 2, 20).unwrap();
+        g.add_edge(1, 3, 5).unwrap();
+        g.add_edge_interval(1, 4, 10, 30).unwrap();
+        g.add_edge(1, 5, 10).unwrap();
+
+        let starts: Vec<u64> = g.get_edges()[&1].iter().map(|e| e.interval.start).collect();
+        assert_eq!(starts, vec![5, 10, 10, 20]);
+        assert_eq!(g.get_neighbors_at(1, 4), Vec::<usize>::new());
+        assert_eq!(g.get_neighbors_at(1, 10), vec![3, 4, 5]);
+        assert_eq!(g.get_neighbors_at(1, 30), vec![3, 5, 2]);
+
+        eprintln!("\n{} for test_add_edge_out_of_order:", std::any::type_name::<G>());
+        g.print();
+    }
+
+    pub fn test_get_neighbors_no_edge<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node(1);
+        let neighbors = g.get_neighbors_at(1, 10);
         assert!(neighbors.is_empty());
 
-        eprintln!("\nTG for test_get_neighbors_no_edge:");
-        tg.print();
+        eprintln!("\n{} for test_get_neighbors_no_edge:", std::any::type_name::<G>());
+        g.print();
     }
     
-    #[test]
-    fn test_get_neighbors_nonexistent_node() {
-        let tg = TemporalGraph::new();
-        let neighbors = tg.get_neighbors_at(999, 100);
+    pub fn test_get_neighbors_nonexistent_node<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let g = G::default();
+        let neighbors = g.get_neighbors_at(999, 100);
         assert!(neighbors.is_empty());
     }
 
+    pub fn test_remove_node<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node(1);
+        g.add_node(2);
+        g.add_node(3);
+        g.add_edge(1, 2, 5).unwrap();
+        g.add_edge(3, 2, 10).unwrap();
+        assert_eq!(g.remove_node(2), Ok(2));
+        assert!(!g.get_nodes().contains(&2));
+        assert!(g.get_edges().get(&1).unwrap().is_empty());
+        assert!(g.get_edges().get(&3).unwrap().is_empty());
+
+        eprintln!("\n{} for test_remove_node:", std::any::type_name::<G>());
+        g.print();
+    }
+
+    pub fn test_remove_node_nonexistent<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        assert_eq!(g.remove_node(42), Err(GraphError::NodeNotFound(42)));
+    }
+
+    pub fn test_remove_edge<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node(1);
+        g.add_node(2);
+        g.add_edge(1, 2, 5).unwrap();
+
+        assert_eq!(g.remove_edge(1, 2, 5), Ok((1, 2, 5)));
+        assert!(g.get_edges().get(&1).unwrap().is_empty());
+
+        eprintln!("\n{} for test_remove_edge:", std::any::type_name::<G>());
+        g.print();
+    }
+
+    pub fn test_remove_edge_nonexistent<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node(1);
+        g.add_node(2);
+        g.add_edge(1, 2, 5).unwrap();
+
+        assert_eq!(g.remove_edge(1, 2, 99), Err(GraphError::EdgeNotFound { src: 1, dst: 2, timestamp: 99 }));
+        assert_eq!(g.remove_edge(2, 1, 5), Err(GraphError::EdgeNotFound { src: 2, dst: 1, timestamp: 5 }));
+        assert_eq!(g.remove_edge(1, 3, 5), Err(GraphError::EdgeNotFound { src: 1, dst: 3, timestamp: 5 }));
+
+        eprintln!("\n{} for test_remove_edge_nonexistent:", std::any::type_name::<G>());
+        g.print();
+    }
+
+    pub fn test_get_in_neighbors_at<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=4).for_each(|i| g.add_node(i));
+        g.add_edge(1, 3, 5).unwrap();
+        g.add_edge_interval(2, 3, 10, 20).unwrap();
+        g.add_edge(3, 4, 1).unwrap();
+
+        for indexed in [false, true] {
+            if indexed {
+                g.enable_in_index();
+            }
+            assert_eq!(g.has_in_index(), indexed);
+            assert!(g.get_in_neighbors_at(3, 4).is_empty());
+            assert_eq!(g.get_in_neighbors_at(3, 5), vec![1]);
+            let mut at_15 = g.get_in_neighbors_at(3, 15);
+            at_15.sort();
+            assert_eq!(at_15, vec![1, 2]);
+            assert_eq!(g.get_in_neighbors_at(3, 20), vec![1]);
+            let mut all_at_15 = g.get_all_neighbors_at(3, 15);
+            all_at_15.sort();
+            assert_eq!(all_at_15, vec![1, 2, 4]);
+        }
+
+        eprintln!("\n{} for test_get_in_neighbors_at:", std::any::type_name::<G>());
+        g.print();
+    }
+
+    pub fn test_all_neighbors_no_duplicates<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node(1);
+        g.add_node(2);
+        g.add_edge(1, 2, 1).unwrap();
+        g.add_edge(2, 1, 1).unwrap();
+        g.add_edge(1, 2, 3).unwrap();
+        assert_eq!(g.get_all_neighbors_at(1, 5), vec![2]);
+    }
+
+    pub fn test_in_index_maintained<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.enable_in_index();
+        (1..=4).for_each(|i| g.add_node(i));
+        g.add_edge(1, 2, 5).unwrap();
+        g.add_edge(3, 2, 7).unwrap();
+        g.add_edge(2, 4, 9).unwrap();
+        g.add_edge(2, 2, 1).unwrap();
+
+        g.remove_edge(3, 2, 7).unwrap();
+        let mut in_2 = g.get_in_neighbors_at(2, 10);
+        in_2.sort();
+        assert_eq!(in_2, vec![1, 2]);
+
+        assert_eq!(g.remove_node(2), Ok(2));
+        assert!(g.get_neighbors_at(1, 10).is_empty());
+        assert!(g.get_in_neighbors_at(4, 10).is_empty());
+        assert!(g.get_in_neighbors_at(2, 10).is_empty());
+        assert!(g.get_edges().get(&2).is_none());
+    }
+
+    pub fn test_add_edge_with_properties<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=3).for_each(|i| g.add_node(i));
+        let mut call = Properties::new();
+        call.insert("kind".into(), "call".into());
+        call.insert("duration".into(), PropertyValue::Int(120));
+        g.add_edge_with(1, 2, Interval::new(5, 50), 120.0, call).unwrap();
+        let mut payment = Properties::new();
+        payment.insert("kind".into(), "payment".into());
+        payment.insert("amount".into(), PropertyValue::Float(9.99));
+        payment.insert("flagged".into(), true.into());
+        g.add_edge_with(1, 3, Interval::starting_at(10), 9.99, payment).unwrap();
+
+        let edges = g.get_edges_at(1, 20);
+        assert_eq!(edges.len(), 2);
+        assert_eq!(edges[0].weight, 120.0);
+        assert_eq!(edges[0].property("duration").and_then(PropertyValue::as_int), Some(120));
+        assert_eq!(edges[1].property("amount").and_then(PropertyValue::as_float), Some(9.99));
+        assert!(edges[1].property("missing").is_none());
+        assert_eq!(g.get_edges_at(1, 60).len(), 1);
+
+        let calls = g.get_neighbors_at_filtered(1, 20, |e| e.property("kind").and_then(PropertyValue::as_str) == Some("call"));
+        assert_eq!(calls, vec![2]);
+        let heavy = g.get_neighbors_at_filtered(1, 20, |e| e.weight > 100.0);
+        assert_eq!(heavy, vec![2]);
+        let flagged = g.get_neighbors_at_filtered(1, 20, |e| e.property("flagged").and_then(PropertyValue::as_bool) == Some(true));
+        assert_eq!(flagged, vec![3]);
+
+        assert_eq!(
+            g.add_edge_with(1, 2, Interval::new(5, 5), 1.0, Properties::new()),
+            Err(GraphError::InvalidInterval { start: 5, end: 5 })
+        );
+
+        eprintln!("\n{} for test_add_edge_with_properties:", std::any::type_name::<G>());
+        g.print();
+    }
+
+    pub fn test_plain_edges_default_weight<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node(1);
+        g.add_node(2);
+        g.add_edge(1, 2, 5).unwrap();
+        let edges = g.get_edges_at(1, 5);
+        assert_eq!(edges[0].weight, DEFAULT_WEIGHT);
+        assert!(edges[0].properties.is_empty());
+    }
+
+    pub fn test_node_lifetimes<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node(1);
+        g.add_node_at(2, 10);
+        g.add_node_at(3, 0);
+        g.add_edge(1, 2, 0).unwrap();
+        g.add_edge(1, 3, 0).unwrap();
+        g.add_edge(3, 1, 0).unwrap();
+        assert_eq!(g.remove_node_at(3, 100), Ok(3));
+
+        let mut alive_at_50 = g.nodes_at(50);
+        alive_at_50.sort();
+        assert_eq!(alive_at_50, vec![1, 2, 3]);
+        assert_eq!(g.nodes_at(5).len(), 2);
+        assert_eq!(g.nodes_at(100).len(), 2);
+
+        assert_eq!(g.get_neighbors_at(1, 5), vec![3]);
+        assert_eq!(g.get_neighbors_at(1, 50), vec![2, 3]);
+        assert_eq!(g.get_neighbors_at(1, 100), vec![2]);
+        assert_eq!(g.get_in_neighbors_at(1, 50), vec![3]);
+        assert!(g.get_in_neighbors_at(1, 100).is_empty());
+        assert!(g.get_neighbors_at(3, 100).is_empty());
+
+        // History is preserved after deletion.
+        assert!(g.get_nodes().contains(&3));
+        assert_eq!(g.node_lifetime(&3), Some(Lifetime { born: Some(0), died: Some(100) }));
+        assert_eq!(g.node_lifetime(&1), Some(Lifetime::default()));
+        assert_eq!(g.node_lifetime(&42), None);
+        assert!(g.is_alive_at(&3, 99));
+        assert!(!g.is_alive_at(&3, 100));
+
+        eprintln!("\n{} for test_node_lifetimes:", std::any::type_name::<G>());
+        g.print();
+    }
+
+    pub fn test_remove_node_at_errors<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node_at(1, 10);
+        assert_eq!(g.remove_node_at(2, 20), Err(GraphError::NodeNotFound(2)));
+        assert_eq!(g.remove_node_at(1, 10), Err(GraphError::InvalidInterval { start: 10, end: 10 }));
+        assert_eq!(g.remove_node(1), Ok(1));
+        assert_eq!(g.node_lifetime(&1), None);
+    }
+
+    pub fn test_directed_counts<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=3).for_each(|i| g.add_node(i));
+        g.add_edge(1, 2, 1).unwrap();
+        g.add_edge(2, 1, 2).unwrap();
+        g.add_edge(1, 3, 3).unwrap();
+        g.add_edge(3, 3, 4).unwrap();
+
+        assert!(g.is_directed());
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.out_degree(&1), 2);
+        assert_eq!(g.in_degree(&1), 1);
+        assert_eq!(g.degree(&1), 3);
+        assert_eq!(g.degree(&3), 3);
+        g.enable_in_index();
+        assert_eq!(g.in_degree(&3), 2);
+    }
+
+    pub fn test_time_window_queries<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=4).for_each(|i| g.add_node(i));
+        g.add_edge_interval(1, 2, 0, 10).unwrap();
+        g.add_edge(1, 3, 15).unwrap();
+        g.add_edge_interval(1, 2, 20, 30).unwrap();
+        g.add_edge(2, 4, 25).unwrap();
+
+        assert_eq!(g.get_neighbors_between(1, 5, 16), vec![2, 3]);
+        assert_eq!(g.get_neighbors_between(1, 10, 15), Vec::<usize>::new());
+        assert_eq!(g.get_neighbors_between(1, 12, 40), vec![3, 2]);
+        assert!(g.get_neighbors_between(1, 16, 16).is_empty());
+
+        let mut in_range: Vec<(usize, usize, u64)> = g
+            .edges_in_range(9, 21)
+            .into_iter()
+            .map(|(src, edge)| (src, edge.dst, edge.interval.start))
+            .collect();
+        in_range.sort();
+        assert_eq!(in_range, vec![(1, 2, 0), (1, 2, 20), (1, 3, 15)]);
+        assert_eq!(g.edges_in_range(0, 100).len(), 4);
+        assert!(g.edges_in_range(30, 30).is_empty());
+
+        assert_eq!(g.first_contact(&1, &2), Some(0));
+        assert_eq!(g.last_contact(&1, &2), Some(20));
+        assert_eq!(g.first_contact(&1, &3), g.last_contact(&1, &3));
+        assert_eq!(g.first_contact(&2, &1), None);
+        assert_eq!(g.last_contact(&9, &1), None);
+    }
+
+    pub fn test_window_respects_lifetimes<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node(1);
+        g.add_node_at(2, 50);
+        g.add_edge(1, 2, 0).unwrap();
+        assert!(g.get_neighbors_between(1, 0, 50).is_empty());
+        assert_eq!(g.get_neighbors_between(1, 0, 51), vec![2]);
+        assert!(g.edges_in_range(0, 50).is_empty());
+        assert_eq!(g.edges_in_range(0, 51).len(), 1);
+        assert_eq!(g.first_contact(&1, &2), Some(50));
+
+        g.add_node_at(3, 0);
+        g.add_edge_interval(1, 3, 0, 5).unwrap();
+        g.add_edge_interval(1, 3, 30, 40).unwrap();
+        g.remove_node_at(3, 20).unwrap();
+        // 3 is gone before the second edge starts.
+        assert_eq!(g.last_contact(&1, &3), Some(0));
+        assert!(g.edges_in_range(25, 35).is_empty());
+    }
+
+    pub fn test_snapshot_at<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=3).for_each(|i| g.add_node(i));
+        g.add_node_at(4, 10);
+        g.add_edge_interval(1, 2, 0, 10).unwrap();
+        g.add_edge(1, 2, 5).unwrap();
+        g.add_edge(2, 3, 5).unwrap();
+        g.add_edge(3, 4, 0).unwrap();
+
+        let snap = g.snapshot_at(5);
+        assert_eq!(snap.node_count(), 3);
+        assert_eq!(snap.edge_count(), 2);
+        assert_eq!(snap.index_of(&4), None);
+        assert_eq!(snap.neighbors_of(&1), vec![&2]);
+        let one = snap.index_of(&1).unwrap();
+        assert_eq!(snap.neighbor_weights(one), &[2.0]);
+        let reached: Vec<usize> = snap.bfs(one).into_iter().map(|i| *snap.node(i)).collect();
+        assert_eq!(reached, vec![1, 2, 3]);
+
+        let later = g.snapshot_at(10);
+        assert_eq!(later.node_count(), 4);
+        assert_eq!(later.neighbor_weights(later.index_of(&1).unwrap()), &[1.0]);
+        assert_eq!(later.neighbors_of(&3), vec![&4]);
+        assert!(g.snapshot_at(0).neighbors_of(&2).is_empty());
+    }
+
+    pub fn test_snapshot_between<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=3).for_each(|i| g.add_node(i));
+        g.add_edge_interval(1, 2, 0, 5).unwrap();
+        g.add_edge_interval(2, 3, 10, 15).unwrap();
+        g.remove_node_at(1, 8).unwrap();
+
+        let snap = g.snapshot_between(4, 12);
+        assert_eq!(snap.node_count(), 3);
+        assert_eq!(snap.edge_count(), 2);
+        let one = snap.index_of(&1).unwrap();
+        assert_eq!(snap.bfs(one).len(), 3);
+
+        let late = g.snapshot_between(9, 20);
+        assert_eq!(late.index_of(&1), None);
+        assert_eq!(late.edge_count(), 1);
+        assert_eq!(g.snapshot_between(5, 10).edge_count(), 0);
+        assert_eq!(g.snapshot_between(7, 7).node_count(), 0);
+    }
+
+    pub fn test_earliest_arrival<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=5).for_each(|i| g.add_node(i));
+        g.add_edge_interval(1, 2, 2, 4).unwrap();
+        g.add_edge(1, 3, 1).unwrap();
+        g.add_edge_interval(2, 4, 0, 3).unwrap();
+        g.add_edge(3, 4, 6).unwrap();
+        g.add_edge_interval(4, 5, 0, 5).unwrap();
+
+        let ea = earliest_arrival(&g, 1, 0);
+        assert_eq!(ea.arrival_time(&1), Some(0));
+        assert_eq!(ea.arrival_time(&2), Some(2));
+        assert_eq!(ea.arrival_time(&3), Some(1));
+        assert_eq!(ea.arrival_time(&4), Some(2));
+        assert_eq!(ea.arrival_time(&5), Some(2));
+        assert_eq!(ea.path_to(&1), Some(vec![]));
+        let route: Vec<(usize, usize, u64)> = ea
+            .path_to(&5)
+            .unwrap()
+            .into_iter()
+            .map(|hop| (hop.src, hop.dst, hop.departure))
+            .collect();
+        assert_eq!(route, vec![(1, 2, 2), (2, 4, 2), (4, 5, 2)]);
+
+        // Leaving too late misses the 2 -> 4 window and the 4 -> 5 edge.
+        let late = earliest_arrival(&g, 1, 3);
+        assert_eq!(late.arrival_time(&4), Some(6));
+        assert!(!late.is_reachable(&5));
+        assert_eq!(late.path_to(&5), None);
+        assert_eq!(earliest_arrival(&g, 5, 0).arrivals().len(), 1);
+        assert!(earliest_arrival(&g, 9, 0).arrivals().is_empty());
+    }
+
+    pub fn test_earliest_arrival_waits_for_birth<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        g.add_node(1);
+        g.add_node_at(2, 7);
+        g.add_node(3);
+        g.add_edge(1, 2, 0).unwrap();
+        g.add_edge(2, 3, 0).unwrap();
+        g.remove_node_at(3, 5).unwrap();
+
+        let ea = earliest_arrival(&g, 1, 0);
+        assert_eq!(ea.arrival_time(&2), Some(7));
+        assert!(!ea.is_reachable(&3));
+    }
+
+    pub fn test_latest_departure<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=3).for_each(|i| g.add_node(i));
+        g.add_edge_with_duration(1, 2, Interval::new(0, 10), 2).unwrap();
+        g.add_edge_with_duration(2, 3, Interval::new(5, 20), 1).unwrap();
+        g.add_edge_with_duration(1, 3, Interval::new(0, 3), 5).unwrap();
+
+        let ld = latest_departure(&g, 3, 12);
+        assert_eq!(ld.departure_time(&3), Some(12));
+        assert_eq!(ld.departure_time(&2), Some(11));
+        assert_eq!(ld.departure_time(&1), Some(9));
+        let route: Vec<(usize, usize, u64, u64)> = ld
+            .path_from(&1)
+            .unwrap()
+            .into_iter()
+            .map(|hop| (hop.src, hop.dst, hop.departure, hop.arrival))
+            .collect();
+        assert_eq!(route, vec![(1, 2, 9, 11), (2, 3, 11, 12)]);
+
+        // Only the slow direct edge still makes an early deadline.
+        let early = latest_departure(&g, 3, 5);
+        assert!(!early.can_reach(&2));
+        assert_eq!(early.departure_time(&1), Some(0));
+        assert_eq!(early.path_from(&1).unwrap().len(), 1);
+        assert!(latest_departure(&g, 3, 4).departures().len() == 1);
+    }
+
+    pub fn test_earliest_arrival_with_durations<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=3).for_each(|i| g.add_node(i));
+        g.add_edge_with_duration(1, 2, Interval::starting_at(0), 4).unwrap();
+        g.add_edge_with_duration(2, 3, Interval::new(0, 5), 1).unwrap();
+        g.add_edge_with_duration(1, 3, Interval::starting_at(1), 9).unwrap();
+        assert_eq!(
+            g.add_edge_with_duration(1, 3, Interval::new(4, 4), 1),
+            Err(GraphError::InvalidInterval { start: 4, end: 4 })
+        );
+
+        let ea = earliest_arrival(&g, 1, 0);
+        assert_eq!(ea.arrival_time(&2), Some(4));
+        assert_eq!(ea.arrival_time(&3), Some(5));
+        assert_eq!(earliest_arrival(&g, 1, 1).arrival_time(&3), Some(10));
+    }
+
+    pub fn test_fastest_paths<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=3).for_each(|i| g.add_node(i));
+        g.add_edge_with_duration(1, 2, Interval::new(0, 100), 1).unwrap();
+        g.add_edge_with_duration(2, 3, Interval::new(50, 51), 1).unwrap();
+        g.add_edge_with_duration(1, 3, Interval::new(10, 11), 60).unwrap();
+
+        let fp = fastest_paths(&g, 1, 0);
+        assert_eq!(fp.duration(&1), Some(0));
+        assert_eq!(fp.duration(&2), Some(1));
+        assert_eq!(fp.duration(&3), Some(2));
+        let route: Vec<(usize, usize, u64, u64)> = fp
+            .path_to(&3)
+            .unwrap()
+            .into_iter()
+            .map(|hop| (hop.src, hop.dst, hop.departure, hop.arrival))
+            .collect();
+        assert_eq!(route, vec![(1, 2, 49, 50), (2, 3, 50, 51)]);
+        assert!(!fastest_paths(&g, 1, 51).is_reachable(&3));
+        assert!(fastest_paths(&g, 9, 0).durations().is_empty());
+    }
+
+    pub fn test_fastest_paths_inside_intervals<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=3).for_each(|i| g.add_node(i));
+        g.add_edge_with_duration(1, 3, Interval::new(0, 1), 10).unwrap();
+        g.add_edge_with_duration(1, 2, Interval::new(0, 100), 0).unwrap();
+        g.add_edge_with_duration(2, 3, Interval::new(20, 21), 0).unwrap();
+
+        let fp = fastest_paths(&g, 1, 0);
+        assert_eq!(fp.duration(&3), Some(0));
+        let route: Vec<(usize, usize, u64, u64)> = fp
+            .path_to(&3)
+            .unwrap()
+            .into_iter()
+            .map(|hop| (hop.src, hop.dst, hop.departure, hop.arrival))
+            .collect();
+        assert_eq!(route, vec![(1, 2, 20, 20), (2, 3, 20, 20)]);
+
+        // Against the best of an earliest-arrival search from every start.
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+        for _ in 0..20 {
+            let mut g = G::default();
+            (0..6).for_each(|i| g.add_node(i));
+            for _ in 0..12 {
+                let start = rng.gen_range(0..30);
+                let end = start + rng.gen_range(1..15);
+                let (src, dst) = (rng.gen_range(0..6), rng.gen_range(0..6));
+                g.add_edge_with_duration(src, dst, Interval::new(start, end), rng.gen_range(0..5)).unwrap();
+            }
+            let fp = fastest_paths(&g, 0, 0);
+            for node in 0..6 {
+                let best = (0..=45)
+                    .filter_map(|start| Some(earliest_arrival(&g, 0, start).arrival_time(&node)? - start))
+                    .min();
+                assert_eq!(fp.duration(&node), best, "node {node}");
+            }
+        }
+    }
+
+    pub fn test_fewest_hops<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=4).for_each(|i| g.add_node(i));
+        g.add_edge(1, 2, 0).unwrap();
+        g.add_edge(2, 3, 0).unwrap();
+        g.add_edge(3, 4, 0).unwrap();
+        g.add_edge_interval(1, 4, 10, 11).unwrap();
+
+        let fh = fewest_hops(&g, 1, 0);
+        assert_eq!(fh.hop_count(&1), Some(0));
+        assert_eq!(fh.hop_count(&3), Some(2));
+        assert_eq!(fh.hop_count(&4), Some(1));
+        assert_eq!(fh.arrival_time(&4), Some(10));
+        assert_eq!(earliest_arrival(&g, 1, 0).arrival_time(&4), Some(0));
+        assert_eq!(fh.path_to(&4).unwrap().len(), 1);
+        let route: Vec<usize> = fh.path_to(&3).unwrap().into_iter().map(|hop| hop.dst).collect();
+        assert_eq!(route, vec![2, 3]);
+        assert_eq!(fewest_hops(&g, 1, 11).hop_count(&4), Some(3));
+    }
+
+    pub fn test_reachable_from<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=5).for_each(|i| g.add_node(i));
+        g.add_edge_interval(1, 2, 2, 3).unwrap();
+        g.add_edge_interval(2, 3, 5, 6).unwrap();
+        g.add_edge_interval(3, 4, 1, 2).unwrap();
+        g.add_edge_with_duration(2, 5, Interval::new(2, 4), 6).unwrap();
+
+        let reach = |node, t_start, t_end| {
+            let mut nodes: Vec<usize> = reachable_from(&g, node, t_start, t_end).into_iter().collect();
+            nodes.sort();
+            nodes
+        };
+        // 3 -> 4 is over before 3 can be reached.
+        assert_eq!(reach(1, 0, 10), vec![2, 3, 5]);
+        assert_eq!(reach(1, 0, 7), vec![2, 3]);
+        assert_eq!(reach(1, 0, 4), vec![2]);
+        // Windows are half-open, so arriving at `t_end` is too late.
+        assert_eq!(reach(1, 0, 5), vec![2]);
+        assert!(reach(1, 0, 2).is_empty());
+        assert!(reach(1, 3, 10).is_empty());
+        assert_eq!(reach(3, 0, 10), vec![4]);
+        assert!(reach(1, 5, 2).is_empty());
+        assert!(reach(9, 0, 10).is_empty());
+    }
+
+    pub fn test_weak_components_at<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=5).for_each(|i| g.add_node(i));
+        g.add_node_at(6, 10);
+        g.add_edge(1, 2, 0).unwrap();
+        g.add_edge(3, 2, 0).unwrap();
+        g.add_edge_interval(4, 5, 0, 5).unwrap();
+        g.add_edge(5, 6, 0).unwrap();
+
+        let early = weak_components_at(&g, 1);
+        assert_eq!(early.len(), 5);
+        assert_eq!(early[&1], early[&3]);
+        assert_eq!(early[&4], early[&5]);
+        assert_ne!(early[&1], early[&4]);
+        assert_eq!(early.values().max(), Some(&1));
+
+        let late = weak_components_at(&g, 10);
+        assert_eq!(late[&5], late[&6]);
+        assert_ne!(late[&4], late[&5]);
+        assert_eq!(late.values().max(), Some(&2));
+    }
+
+    pub fn test_strong_temporal_components<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=4).for_each(|i| g.add_node(i));
+        g.add_edge_interval(1, 2, 1, 2).unwrap();
+        g.add_edge_interval(2, 1, 3, 4).unwrap();
+        // 3 reaches 2 before 2 reaches 3; the two journeys need not line up.
+        g.add_edge_interval(3, 2, 1, 2).unwrap();
+        g.add_edge_interval(2, 3, 5, 6).unwrap();
+        g.add_edge_interval(3, 4, 7, 8).unwrap();
+
+        let components = strong_temporal_components(&g, 0, 10);
+        assert_eq!(components.len(), 4);
+        assert_eq!(components[&1], components[&2]);
+        assert_eq!(components[&2], components[&3]);
+        assert_ne!(components[&3], components[&4]);
+
+        let short = strong_temporal_components(&g, 0, 2);
+        assert_ne!(short[&1], short[&2]);
+        assert_eq!(short.values().max(), Some(&3));
+    }
+
+    pub fn test_count_motifs<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=4).for_each(|i| g.add_node(i));
+        g.add_edge(1, 2, 1).unwrap();
+        g.add_edge(2, 1, 2).unwrap();
+        g.add_edge(1, 2, 3).unwrap();
+        g.add_edge(2, 3, 10).unwrap();
+        g.add_edge(3, 1, 11).unwrap();
+        g.add_edge(1, 2, 12).unwrap();
+        g.add_edge(4, 4, 11).unwrap();
+
+        let pair = Motif::new([(0, 1), (1, 0), (0, 1)]).unwrap();
+        let triangle = Motif::new([(0, 1), (1, 2), (2, 0)]).unwrap();
+        let counts = count_motifs(&g, 2);
+        assert_eq!(counts.get(&pair), 1);
+        assert_eq!(counts.get(&triangle), 1);
+        assert_eq!(counts.total_of(MotifKind::Triangle), 1);
+        assert_eq!(counts.total_of(MotifKind::Star), 0);
+        // The self-loop at 11 is ignored.
+        assert_eq!(counts.total(), 2);
+
+        let tight = count_motifs(&g, 1);
+        assert_eq!(tight.total(), 0);
+        assert!(count_motifs(&g, 100).total() > counts.total());
+    }
+
+    pub fn test_temporal_pagerank<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=4).for_each(|i| g.add_node(i));
+        g.add_edge(1, 2, 1).unwrap();
+        g.add_edge_interval(2, 3, 2, 3).unwrap();
+        let config = PageRankConfig::default();
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+        let early = temporal_pagerank(&g, 1, config);
+        assert_eq!(early.len(), 4);
+        assert!(close(early[&1], 0.15 / 0.2775));
+        assert!(close(early[&2], 0.1275 / 0.2775));
+        assert_eq!(early[&3], 0.0);
+
+        let scores = temporal_pagerank(&g, 5, config);
+        assert!(close(scores.values().sum(), 1.0));
+        assert!(close(scores[&1], 0.15 / 0.6091875));
+        assert!(close(scores[&3], 0.1816875 / 0.6091875));
+        assert_eq!(scores[&4], 0.0);
+        assert!(temporal_pagerank(&g, 0, config).values().all(|&score| score == 0.0));
+
+        // Without the 1 -> 2 -> 3 walk in time order, 3 scores lower.
+        let mut reversed = G::default();
+        (1..=3).for_each(|i| reversed.add_node(i));
+        reversed.add_edge(2, 3, 1).unwrap();
+        reversed.add_edge(1, 2, 2).unwrap();
+        assert!(temporal_pagerank(&reversed, 5, config)[&3] < scores[&3]);
+
+        let mut stream = TemporalPageRank::new(PageRankConfig { alpha: 0.85, beta: 1.0 });
+        stream.process_edge(&1, &2);
+        stream.process_edge(&2, &3);
+        assert!(close(stream.raw_scores()[&3], 0.85 * (0.15 + 0.85 * 0.15)));
+    }
+
+    pub fn test_temporal_centrality<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=5).for_each(|i| g.add_node(i));
+        g.add_edge(1, 2, 1).unwrap();
+        g.add_edge(1, 3, 1).unwrap();
+        g.add_edge(2, 4, 2).unwrap();
+        g.add_edge(3, 4, 2).unwrap();
+        g.add_edge(4, 5, 3).unwrap();
+        g.add_edge_interval(5, 1, 0, 1).unwrap();
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+        // 5 -> 1 is over before 5 is reached, so nothing passes through 5.
+        let betweenness = temporal_betweenness(&g, 0, 10);
+        for (node, expected) in [(1, 3.0), (2, 1.5), (3, 1.5), (4, 3.0), (5, 0.0)] {
+            assert!(close(betweenness[&node], expected), "node {}: {}", node, betweenness[&node]);
+        }
+        let betweenness = temporal_betweenness(&g, 0, 3);
+        for (node, expected) in [(1, 3.0), (2, 1.0), (3, 1.0), (4, 0.0), (5, 0.0)] {
+            assert!(close(betweenness[&node], expected), "node {}: {}", node, betweenness[&node]);
+        }
+        // Arriving at 4 at time 2 falls outside `[0, 2)`.
+        let betweenness = temporal_betweenness(&g, 0, 2);
+        for (node, expected) in [(1, 2.0), (2, 0.0), (3, 0.0), (4, 0.0), (5, 0.0)] {
+            assert!(close(betweenness[&node], expected), "node {}: {}", node, betweenness[&node]);
+        }
+
+        let closeness = temporal_closeness(&g, 0, 10);
+        assert_eq!(closeness.len(), 5);
+        assert!(close(closeness[&1], (0.5 + 0.5 + 1.0 / 3.0 + 0.25) / 4.0));
+        assert!(close(closeness[&5], (1.0 + 0.5 + 0.5 + 1.0 / 3.0) / 4.0));
+        assert!(close(closeness[&4], 0.25 / 4.0));
+        assert_eq!(temporal_closeness_of(&g, &5, 1, 10), 0.0);
+        assert_eq!(temporal_closeness_of(&g, &1, 5, 2), 0.0);
+        assert_eq!(temporal_closeness_of(&g, &1, 1, 1), 0.0);
+    }
+
+    pub fn test_core_evolution<G: TemporalGraphOps<Node = NodeId, Time = Timestamp> + Default>() {
+        let mut g = G::default();
+        (1..=6).for_each(|i| g.add_node(i));
+        g.add_node_at(7, 7);
+        for (a, b) in [(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4), (2, 1)] {
+            g.add_edge_interval(a, b, 0, 5).unwrap();
+        }
+        g.add_edge(5, 1, 0).unwrap();
+        g.add_edge(5, 5, 0).unwrap();
+        g.add_edge(5, 6, 6).unwrap();
+        g.add_edge(6, 1, 6).unwrap();
+
+        let cores = core_numbers(&g, 0, 5);
+        assert_eq!(cores.len(), 6);
+        for (node, expected) in [(1, 3), (2, 3), (3, 3), (4, 3), (5, 1), (6, 0)] {
+            assert_eq!(cores[&node], expected, "node {}", node);
+        }
+        assert_eq!(k_core(&g, 3, 0, 5), HashSet::from([1, 2, 3, 4]));
+        assert!(k_core(&g, 4, 0, 5).is_empty());
+
+        let windows = sliding_windows(0, 10, 5, 5);
+        assert_eq!(windows, vec![(0, 5), (5, 10)]);
+        let evolution = core_evolution(&g, &windows);
+        assert_eq!(evolution.len(), 2);
+        assert_eq!(evolution.core_number(1, &1), Some(2));
+        assert_eq!(evolution.core_number(1, &2), Some(0));
+        assert_eq!(evolution.trajectory(&5), vec![Some(1), Some(2)]);
+        assert_eq!(evolution.trajectory(&7), vec![None, Some(0)]);
+
+        let sorted = |mut nodes: Vec<&usize>| {
+            nodes.sort();
+            nodes.into_iter().copied().collect::<Vec<_>>()
+        };
+        assert_eq!(sorted(evolution.joined_core(0, 3)), vec![1, 2, 3, 4]);
+        assert!(evolution.left_core(0, 3).is_empty());
+        assert_eq!(sorted(evolution.joined_core(1, 2)), vec![5, 6]);
+        assert_eq!(sorted(evolution.left_core(1, 2)), vec![2, 3, 4]);
+        assert!(sliding_windows(0, 10, 4, 0).is_empty());
+    }
+
+    /// Instantiates every generic test above once per graph backend.
+    macro_rules! instantiate_graph_tests {
+        ($($name:ident),* $(,)?) => {
+            mod tg_unit_tests {
+                use crate::sequential::TemporalGraph;
+                $( #[test] fn $name() { super::$name::<TemporalGraph>(); } )*
+            }
+
+            mod cg_unit_tests {
+                use crate::chrono::ChronoGraph;
+                $( #[test] fn $name() { super::$name::<ChronoGraph>(); } )*
+            }
+        };
+    }
+
+    instantiate_graph_tests!(
+        test_add_node,
+        test_add_edge_success,
+        test_add_edge_missing_node,
+        test_error_propagation,
+        test_get_neighbors_at,
+        test_add_edge_interval,
+        test_add_edge_interval_invalid,
+        test_remove_edge_interval,
+        test_add_edge_out_of_order,
+        test_get_neighbors_no_edge,
+        test_get_neighbors_nonexistent_node,
+        test_remove_node,
+        test_remove_node_nonexistent,
+        test_remove_edge,
+        test_remove_edge_nonexistent,
+        test_get_in_neighbors_at,
+        test_all_neighbors_no_duplicates,
+        test_in_index_maintained,
+        test_add_edge_with_properties,
+        test_plain_edges_default_weight,
+        test_node_lifetimes,
+        test_remove_node_at_errors,
+        test_directed_counts,
+        test_time_window_queries,
+        test_window_respects_lifetimes,
+        test_snapshot_at,
+        test_snapshot_between,
+        test_earliest_arrival,
+        test_earliest_arrival_waits_for_birth,
+        test_latest_departure,
+        test_earliest_arrival_with_durations,
+        test_fastest_paths,
+        test_fastest_paths_inside_intervals,
+        test_fewest_hops,
+        test_reachable_from,
+        test_weak_components_at,
+        test_strong_temporal_components,
+        test_count_motifs,
+        test_temporal_pagerank,
+        test_temporal_centrality,
+        test_core_evolution,
+    );
+}
+
+#[cfg(test)]
+mod undirected_tests {
+    use super::error::GraphError;
+    use super::ops::TemporalGraphOps;
+    use super::sequential::TemporalGraph;
+    use super::chrono::ChronoGraph;
+    use super::types::{Directionality, NodeId, Timestamp};
+
+    fn check_symmetric_queries<G: TemporalGraphOps<Node = NodeId, Time = Timestamp>>(mut g: G) {
+        assert_eq!(g.directionality(), Directionality::Undirected);
+        (1..=3).for_each(|i| g.add_node(i));
+        g.add_edge(1, 2, 5).unwrap();
+        g.add_edge_interval(3, 1, 10, 20).unwrap();
+
+        assert_eq!(g.get_neighbors_at(2, 5), vec![1]);
+        let mut at_15 = g.get_neighbors_at(1, 15);
+        at_15.sort();
+        assert_eq!(at_15, vec![2, 3]);
+        assert_eq!(g.get_in_neighbors_at(1, 15).len(), 2);
+        assert_eq!(g.get_all_neighbors_at(1, 15).len(), 2);
+        assert_eq!(g.get_neighbors_at(1, 20), vec![2]);
+
+        eprintln!("\n{} for check_symmetric_queries:", std::any::type_name::<G>());
+        g.print();
+    }
+
+    fn check_counts<G: TemporalGraphOps<Node = NodeId, Time = Timestamp>>(mut g: G) {
+        (1..=3).for_each(|i| g.add_node(i));
+        g.add_edge(1, 2, 1).unwrap();
+        g.add_edge(2, 3, 2).unwrap();
+        g.add_edge(3, 3, 3).unwrap();
+
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(g.degree(&1), 1);
+        assert_eq!(g.degree(&2), 2);
+        assert_eq!(g.degree(&3), 2);
+        assert_eq!(g.in_degree(&2), g.out_degree(&2));
+    }
+
+    fn check_removal<G: TemporalGraphOps<Node = NodeId, Time = Timestamp>>(mut g: G) {
+        (1..=3).for_each(|i| g.add_node(i));
+        g.add_edge(1, 2, 1).unwrap();
+        g.add_edge(2, 3, 2).unwrap();
+
+        // Either endpoint order identifies the edge.
+        assert_eq!(g.remove_edge(2, 1, 1), Ok((2, 1, 1)));
+        assert!(g.get_neighbors_at(1, 5).is_empty());
+        assert_eq!(g.get_neighbors_at(2, 5), vec![3]);
+        assert_eq!(g.remove_edge(1, 2, 1), Err(GraphError::EdgeNotFound { src: 1, dst: 2, timestamp: 1 }));
+
+        assert_eq!(g.remove_node(3), Ok(3));
+        assert!(g.get_neighbors_at(2, 5).is_empty());
+        assert_eq!(g.edge_count(), 0);
+        g.enable_in_index();
+        assert!(!g.has_in_index());
+    }
+
+    fn check_window_queries<G: TemporalGraphOps<Node = NodeId, Time = Timestamp>>(mut g: G) {
+        (1..=3).for_each(|i| g.add_node(i));
+        g.add_edge(1, 2, 5).unwrap();
+        g.add_edge(3, 3, 6).unwrap();
+
+        assert_eq!(g.edges_in_range(0, 10).len(), 2);
+        assert!(g.edges_in_range(0, 10).iter().all(|(_, edge)| !edge.is_mirror()));
+        assert_eq!(g.get_neighbors_between(2, 0, 10), vec![1]);
+        assert_eq!(g.first_contact(&2, &1), Some(5));
+    }
+
+    fn check_snapshot<G: TemporalGraphOps<Node = NodeId, Time = Timestamp>>(mut g: G) {
+        (1..=3).for_each(|i| g.add_node(i));
+        g.add_edge(1, 2, 1).unwrap();
+        g.add_edge(2, 3, 2).unwrap();
+        g.add_edge(3, 3, 3).unwrap();
+
+        let snap = g.snapshot_at(5);
+        assert_eq!(snap.edge_count(), 3);
+        assert_eq!(snap.neighbors_of(&2).len(), 2);
+        let three = snap.index_of(&3).unwrap();
+        let one = snap.index_of(&1).unwrap();
+        assert_eq!(snap.bfs(three).len(), 3);
+        assert!(snap.has_edge(three, three));
+        assert!(!snap.has_edge(one, three));
+        assert_eq!(g.snapshot_between(0, 2).edge_count(), 1);
+    }
+
     #[test]
-    fn test_remove_node() {
-        let mut tg = TemporalGraph::new();
-        tg.add_node(1);
-        tg.add_node(2);
-        tg.add_node(3);
-        tg.add_edge(1, 2, 5).unwrap();
-        tg.add_edge(3, 2, 10).unwrap();
-        assert_eq!(tg.remove_node(2), Ok(2));
-        assert!(!tg.get_nodes().contains(&2));
-        assert!(tg.get_edges().get(&1).unwrap().is_empty());
-        assert!(tg.get_edges().get(&3).unwrap().is_empty());
+    fn test_tg_undirected_snapshot() {
+        check_snapshot(TemporalGraph::new_undirected());
+    }
 
-        eprintln!("\nTG for test_remove_node:");
-        tg.print();
+    #[test]
+    fn test_cg_undirected_snapshot() {
+        check_snapshot(ChronoGraph::new_undirected());
     }
 
     #[test]
-    fn test_remove_node_nonexistent() {
-        let mut tg = TemporalGraph::new();
-        assert_eq!(tg.remove_node(42), Err(false));
+    fn test_tg_undirected_window_queries() {
+        check_window_queries(TemporalGraph::new_undirected());
+    }
+
+    #[test]
+    fn test_cg_undirected_window_queries() {
+        check_window_queries(ChronoGraph::new_undirected());
+    }
+
+    #[test]
+    fn test_tg_undirected_queries() {
+        check_symmetric_queries(TemporalGraph::new_undirected());
     }
 
     #[test]
-    fn test_remove_edge() {
+    fn test_cg_undirected_queries() {
+        check_symmetric_queries(ChronoGraph::new_undirected());
+    }
+
+    #[test]
+    fn test_tg_undirected_counts() {
+        check_counts(TemporalGraph::new_with_directionality(Directionality::Undirected));
+    }
+
+    #[test]
+    fn test_cg_undirected_counts() {
+        check_counts(ChronoGraph::new_with_directionality(Directionality::Undirected));
+    }
+
+    #[test]
+    fn test_tg_undirected_removal() {
+        check_removal(TemporalGraph::new_undirected());
+    }
+
+    #[test]
+    fn test_cg_undirected_removal() {
+        check_removal(ChronoGraph::new_undirected());
+    }
+}
+
+#[cfg(test)]
+mod pool_tests {
+    use super::chrono::*;
+    use super::ops::TemporalGraphOps;
+    use rayon::ThreadPoolBuilder;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_graphs_with_different_thread_counts() {
+        let two = ChronoGraph::<usize, u64>::new_with_threads(2).unwrap();
+        let three = ChronoGraph::<usize, u64>::new_with_threads(3).unwrap();
+        assert_eq!(two.num_threads(), 2);
+        assert_eq!(three.num_threads(), 3);
+        assert_eq!(two.install(rayon::current_num_threads), 2);
+    }
+
+    #[test]
+    fn test_shared_pool() {
+        let pool = Arc::new(ThreadPoolBuilder::new().num_threads(2).build().unwrap());
+        let mut first = ChronoGraph::new_with_pool(Arc::clone(&pool));
+        let second = ChronoGraph::<usize, u64>::new_with_pool(Arc::clone(&pool));
+        assert!(Arc::ptr_eq(first.pool().unwrap(), second.pool().unwrap()));
+
+        first.add_node(1);
+        first.add_node(2);
+        first.add_edge(1, 2, 5).unwrap();
+        assert_eq!(first.get_neighbors_at(1, 5), vec![2]);
+        assert_eq!(first.edge_count(), 1);
+
+        // Cloned graphs keep sharing the same pool.
+        let cloned = first.clone();
+        assert!(Arc::ptr_eq(cloned.pool().unwrap(), &pool));
+    }
+
+    #[test]
+    fn test_default_uses_global_pool() {
+        let cg = ChronoGraph::<usize, u64>::new();
+        assert!(cg.pool().is_none());
+        assert_eq!(cg.num_threads(), rayon::current_num_threads());
+    }
+}
+
+#[cfg(test)]
+mod multi_source_path_tests {
+    use std::collections::HashMap;
+    use super::chrono::ChronoGraph;
+    use super::cores::sliding_windows;
+    use super::edge::Interval;
+    use super::ops::TemporalGraphOps;
+    use super::pagerank::PageRankConfig;
+    use super::sequential::TemporalGraph;
+
+    fn build_pair() -> (TemporalGraph, ChronoGraph) {
         let mut tg = TemporalGraph::new();
-        tg.add_node(1);
-        tg.add_node(2);
-        tg.add_edge(1, 2, 5).unwrap();
+        let mut cg = ChronoGraph::new_with_threads(2).unwrap();
+        for i in 0..30 {
+            tg.add_node(i);
+            cg.add_node(i);
+        }
+        for i in 0..30usize {
+            for step in [1, 7] {
+                let dst = (i + step) % 30;
+                let start = (i * step % 11) as u64;
+                tg.add_edge_with_duration(i, dst, Interval::new(start, start + 5), step as u64).unwrap();
+                cg.add_edge_with_duration(i, dst, Interval::new(start, start + 5), step as u64).unwrap();
+            }
+        }
+        (tg, cg)
+    }
 
-        assert_eq!(tg.remove_edge(1, 2, 5), Ok((1, 2, 5)));
-        assert!(tg.get_edges().get(&1).unwrap().is_empty());
+    #[test]
+    fn test_many_matches_single_source() {
+        let (tg, cg) = build_pair();
+        let sources: Vec<usize> = (0..30).collect();
+
+        for (source, result) in sources.iter().zip(cg.earliest_arrival_many(&sources, 0)) {
+            assert_eq!(result.source(), source);
+            assert_eq!(result.arrivals(), tg.earliest_arrival(*source, 0).arrivals());
+        }
+        for (source, result) in sources.iter().zip(cg.fastest_paths_many(&sources, 0)) {
+            assert_eq!(result.durations(), tg.fastest_paths(*source, 0).durations());
+        }
+        for (source, result) in sources.iter().zip(cg.fewest_hops_many(&sources, 0)) {
+            assert_eq!(result.hop_counts(), tg.fewest_hops(*source, 0).hop_counts());
+        }
+        for (target, result) in sources.iter().zip(cg.latest_departure_many(&sources, 40)) {
+            assert_eq!(result.departures(), tg.latest_departure(*target, 40).departures());
+        }
+    }
+
+    #[test]
+    fn test_reachability_matrix_matches_single_source() {
+        let (tg, cg) = build_pair();
+        let matrix = cg.reachability_matrix(0, 30);
+        let counts = cg.reachability_counts(0, 30);
+        assert_eq!(matrix.node_count(), 30);
+        for source in 0..30 {
+            let reached = tg.reachable_from(source, 0, 30);
+            assert_eq!(matrix.reachable_count(&source), reached.len());
+            assert_eq!(counts[&source], reached.len());
+            assert!(!matrix.can_reach(&source, &source));
+            for target in 0..30 {
+                assert_eq!(matrix.can_reach(&source, &target), reached.contains(&target));
+            }
+            assert!(matrix.reachable_from(&source).into_iter().all(|node| reached.contains(node)));
+        }
+        assert!(!matrix.can_reach(&0, &99));
+        assert_eq!(matrix.reachable_count(&99), 0);
+    }
 
-        eprintln!("\nTG for test_remove_edge:");
-        tg.print();
+    /// Returns `true` if both labellings group the same nodes together.
+    fn same_partition(a: &HashMap<usize, usize>, b: &HashMap<usize, usize>) -> bool {
+        a.len() == b.len()
+            && a.keys().all(|x| a.keys().all(|y| (a[x] == a[y]) == (b.get(x) == b.get(y))))
     }
 
     #[test]
-    fn test_remove_edge_nonexistent() {
+    fn test_parallel_components_match_sequential() {
+        let (tg, cg) = build_pair();
+        for t in [0, 3, 8, 12] {
+            let sequential = tg.weak_components_at(t);
+            assert!(same_partition(&sequential, &cg.weak_components_at(t)));
+        }
+        for (t_start, t_end) in [(0, 10), (0, 40), (5, 20)] {
+            let sequential = tg.strong_temporal_components(t_start, t_end);
+            assert!(same_partition(&sequential, &cg.strong_temporal_components(t_start, t_end)));
+        }
+    }
+
+    #[test]
+    fn test_parallel_centrality_matches_sequential() {
+        let (tg, cg) = build_pair();
+        for (t_start, t_end) in [(0, 20), (3, 40)] {
+            for (sequential, parallel) in [
+                (tg.temporal_closeness(t_start, t_end), cg.temporal_closeness(t_start, t_end)),
+                (tg.temporal_betweenness(t_start, t_end), cg.temporal_betweenness(t_start, t_end)),
+            ] {
+                assert_eq!(sequential.len(), 30);
+                assert_eq!(parallel.len(), 30);
+                for (node, score) in &sequential {
+                    assert!((score - parallel[node]).abs() < 1e-9);
+                }
+            }
+        }
+        assert!(tg.temporal_betweenness(0, 40).values().any(|&score| score > 0.0));
+    }
+
+    #[test]
+    fn test_parallel_pagerank_matches_sequential() {
+        // Many edges share a start time, so the tie order matters.
+        let (tg, cg) = build_pair();
+        for timestamp in [0, 4, 10] {
+            let sequential = tg.temporal_pagerank(timestamp, PageRankConfig::default());
+            let parallel = cg.temporal_pagerank(timestamp, PageRankConfig::default());
+            assert_eq!(parallel.len(), 30);
+            for (node, score) in &sequential {
+                assert!((score - parallel[node]).abs() < 1e-12);
+            }
+        }
+        assert!(tg.temporal_pagerank(10, PageRankConfig::default()).values().any(|&score| score > 0.0));
+    }
+
+    #[test]
+    fn test_parallel_core_evolution_matches_sequential() {
+        let (tg, cg) = build_pair();
+        let windows = sliding_windows(0, 20, 6, 3);
+        let sequential = tg.core_evolution(&windows);
+        let parallel = cg.core_evolution(&windows);
+        assert_eq!(parallel.windows(), windows.as_slice());
+        for i in 0..windows.len() {
+            assert_eq!(sequential.core_numbers(i), parallel.core_numbers(i));
+        }
+        assert_eq!(&tg.core_numbers(0, 6), sequential.core_numbers(0));
+        assert_eq!(cg.core_numbers(2, 11), tg.core_numbers(2, 11));
+    }
+
+    #[test]
+    fn test_latest_departure_many_uses_in_index() {
+        let (tg, mut cg) = build_pair();
+        cg.enable_in_index();
+        let targets = [3, 17];
+        for (target, result) in targets.iter().zip(cg.latest_departure_many(&targets, 25)) {
+            assert_eq!(result.departures(), tg.latest_departure(*target, 25).departures());
+        }
+    }
+}
+
+#[cfg(test)]
+mod motif_tests {
+    use super::chrono::ChronoGraph;
+    use super::motifs::{Motif, MotifKind, MOTIF_COUNT};
+    use super::ops::TemporalGraphOps;
+    use super::sequential::TemporalGraph;
+    use rand::Rng;
+
+    /// Counts motifs by checking every time-ordered triple of edges.
+    fn brute_force(events: &[(u64, usize, usize)], delta: u64) -> [u64; MOTIF_COUNT] {
+        let mut sorted = events.to_vec();
+        sorted.sort();
+        let mut counts = [0; MOTIF_COUNT];
+        for (i, e1) in sorted.iter().enumerate() {
+            for (j, e2) in sorted.iter().enumerate().skip(i + 1) {
+                for e3 in sorted.iter().skip(j + 1) {
+                    if !(e1.0 < e2.0 && e2.0 < e3.0 && e3.0 - e1.0 <= delta) {
+                        continue;
+                    }
+                    let mut seen = Vec::new();
+                    let mut label = |node: usize| match seen.iter().position(|&x| x == node) {
+                        Some(k) => k as u8,
+                        None => {
+                            seen.push(node);
+                            (seen.len() - 1) as u8
+                        }
+                    };
+                    let edges = [(e1.1, e1.2), (e2.1, e2.2), (e3.1, e3.2)].map(|(a, b)| (label(a), label(b)));
+                    if let Some(motif) = Motif::new(edges) {
+                        counts[motif.index()] += 1;
+                    }
+                }
+            }
+        }
+        counts
+    }
+
+    #[test]
+    fn test_motif_catalogue() {
+        let all = Motif::all();
+        assert_eq!(all.len(), MOTIF_COUNT);
+        assert!(all.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(all.iter().enumerate().all(|(i, motif)| motif.index() == i));
+        let of_kind = |kind| all.iter().filter(|motif| motif.kind() == kind).count();
+        assert_eq!(of_kind(MotifKind::Pair), 4);
+        assert_eq!(of_kind(MotifKind::Star), 24);
+        assert_eq!(of_kind(MotifKind::Triangle), 8);
+        assert_eq!(all[0].to_string(), "0->1, 0->1, 0->1");
+        assert!(Motif::new([(1, 0), (0, 1), (0, 1)]).is_none());
+        assert!(Motif::new([(0, 1), (2, 2), (0, 1)]).is_none());
+        assert!(Motif::new([(0, 1), (0, 3), (0, 1)]).is_none());
+    }
+
+    #[test]
+    fn test_counts_match_brute_force() {
+        let mut rng = rand::thread_rng();
         let mut tg = TemporalGraph::new();
-        tg.add_node(1);
-        tg.add_node(2);
-        tg.add_edge(1, 2, 5).unwrap();
+        let mut cg = ChronoGraph::new_with_threads(3).unwrap();
+        let mut events = Vec::new();
+        for i in 0..8 {
+            tg.add_node(i);
+            cg.add_node(i);
+        }
+        for _ in 0..60 {
+            let (src, dst, ts) = (rng.gen_range(0..8), rng.gen_range(0..8), rng.gen_range(0..40));
+            tg.add_edge(src, dst, ts).unwrap();
+            cg.add_edge(src, dst, ts).unwrap();
+            if src != dst {
+                events.push((ts, src, dst));
+            }
+        }
+        for delta in [0, 3, 10, 50] {
+            let expected = brute_force(&events, delta);
+            assert_eq!(tg.count_motifs(delta).as_array(), &expected);
+            assert_eq!(cg.count_motifs(delta).as_array(), &expected);
+        }
+    }
+
+    #[test]
+    fn test_undirected_edges_count_once() {
+        let mut tg = TemporalGraph::new_undirected();
+        let mut cg = ChronoGraph::new_undirected();
+        for i in 1..=2 {
+            tg.add_node(i);
+            cg.add_node(i);
+        }
+        for t in 1..=3 {
+            tg.add_edge(1, 2, t).unwrap();
+            cg.add_edge(1, 2, t).unwrap();
+        }
+        let repeated = Motif::new([(0, 1), (0, 1), (0, 1)]).unwrap();
+        for counts in [tg.count_motifs(5), cg.count_motifs(5)] {
+            assert_eq!(counts.get(&repeated), 1);
+            assert_eq!(counts.total(), 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod persist_tests {
+    use super::chrono::ChronoGraph;
+    use super::edge::{Interval, Properties};
+    use super::error::PersistError;
+    use super::ops::TemporalGraphOps;
+    use super::persist::{crc32, FORMAT_MAJOR};
+    use super::sequential::TemporalGraph;
+
+    fn build() -> TemporalGraph {
+        let mut g = TemporalGraph::new_with_in_index();
+        (1..=3).for_each(|i| g.add_node(i));
+        g.add_node_at(4, 5);
+        g.add_node_at(5, 2);
+        g.remove_node_at(5, 30).unwrap();
+        g.remove_node_at(3, 40).unwrap();
+        let mut properties = Properties::new();
+        properties.insert("kind".to_string(), "call".into());
+        properties.insert("count".to_string(), (-7i64).into());
+        properties.insert("score".to_string(), 0.25.into());
+        properties.insert("flagged".to_string(), true.into());
+        g.add_edge_with(1, 2, Interval::new(3, 9), 2.5, properties).unwrap();
+        g.add_edge(1, 3, 1).unwrap();
+        g.add_edge_with_duration(2, 4, Interval::starting_at(6), 3).unwrap();
+        g.add_edge(5, 5, 4).unwrap();
+        g
+    }
+
+    /// Compares everything a graph file records.
+    fn assert_same<G: TemporalGraphOps<Node = usize, Time = u64>, H: TemporalGraphOps<Node = usize, Time = u64>>(a: &G, b: &H) {
+        assert_eq!(a.get_nodes(), b.get_nodes());
+        assert_eq!(a.directionality(), b.directionality());
+        assert_eq!(a.has_in_index(), b.has_in_index());
+        for node in a.get_nodes() {
+            assert_eq!(a.node_lifetime(node), b.node_lifetime(node));
+            assert_eq!(a.edges_of(node), b.edges_of(node));
+        }
+    }
+
+    #[test]
+    fn test_round_trip_across_backends() {
+        let g = build();
+        let mut bytes = Vec::new();
+        g.save_to(&mut bytes).unwrap();
+
+        let loaded = TemporalGraph::load_from(bytes.as_slice()).unwrap();
+        assert_same(&g, &loaded);
+        assert_eq!(loaded.get_in_neighbors_at(4, 10), vec![2]);
+        assert_same(&g, &ChronoGraph::<usize, u64>::load_from(bytes.as_slice()).unwrap());
+
+        let mut parallel_bytes = Vec::new();
+        ChronoGraph::<usize, u64>::load_from(bytes.as_slice()).unwrap().save_to(&mut parallel_bytes).unwrap();
+        assert_same(&g, &TemporalGraph::load_from(parallel_bytes.as_slice()).unwrap());
+        assert_eq!(parallel_bytes, bytes);
+    }
+
+    #[test]
+    fn test_saves_are_deterministic() {
+        let save = |order: &[usize]| {
+            let mut g = TemporalGraph::new();
+            order.iter().for_each(|&i| g.add_node(i));
+            for &i in order {
+                g.add_edge(i, i % 5 + 1, i as u64).unwrap();
+            }
+            let mut bytes = Vec::new();
+            g.save_to(&mut bytes).unwrap();
+            bytes
+        };
+        assert_eq!(save(&[1, 2, 3, 4, 5]), save(&[5, 3, 1, 4, 2]));
+    }
 
-        assert_eq!(tg.remove_edge(1, 2, 99), Err(false));
-        assert_eq!(tg.remove_edge(2, 1, 5), Err(false));
-        assert_eq!(tg.remove_edge(1, 3, 5), Err(false));
+    #[test]
+    fn test_undirected_and_typed_round_trip() {
+        let mut g: ChronoGraph<String, i64> = ChronoGraph::new_undirected();
+        for name in ["alice", "bob", "carol"] {
+            g.add_node(name.to_string());
+        }
+        g.add_edge_interval("alice".to_string(), "bob".to_string(), -50, -10).unwrap();
+        g.add_edge("carol".to_string(), "bob".to_string(), i64::MIN).unwrap();
+        let mut bytes = Vec::new();
+        g.save_to(&mut bytes).unwrap();
+
+        let loaded = TemporalGraph::<String, i64>::load_from(bytes.as_slice()).unwrap();
+        assert!(!loaded.is_directed());
+        assert_eq!(loaded.edge_count(), 2);
+        assert_eq!(loaded.get_neighbors_at("bob".to_string(), -20).len(), 2);
+        assert_eq!(loaded.get_neighbors_at("bob".to_string(), 0), vec!["carol".to_string()]);
+    }
 
-        eprintln!("\nTG for test_remove_edge_nonexistent:");
-        tg.print();
+    #[test]
+    fn test_rejects_corrupted_files() {
+        let mut bytes = Vec::new();
+        build().save_to(&mut bytes).unwrap();
+
+        let mut flipped = bytes.clone();
+        flipped[10] ^= 0x40;
+        assert!(matches!(TemporalGraph::<usize, u64>::load_from(flipped.as_slice()), Err(PersistError::ChecksumMismatch { .. })));
+        let truncated = &bytes[..bytes.len() - 7];
+        assert!(matches!(TemporalGraph::<usize, u64>::load_from(truncated), Err(PersistError::ChecksumMismatch { .. })));
+        assert!(matches!(TemporalGraph::<usize, u64>::load_from(&b"GRAPH"[..]), Err(PersistError::BadMagic)));
+        assert!(matches!(TemporalGraph::<usize, u64>::load_from(&[][..]), Err(PersistError::BadMagic)));
+
+        let mut newer = bytes.clone();
+        newer[4] = FORMAT_MAJOR + 1;
+        let err = TemporalGraph::<usize, u64>::load_from(newer.as_slice()).unwrap_err();
+        assert!(matches!(err, PersistError::UnsupportedVersion { major, .. } if major == FORMAT_MAJOR + 1));
+        assert_eq!(err.to_string(), format!("unsupported format version {}.0", FORMAT_MAJOR + 1));
+
+        // A valid checksum over malformed contents: a section longer than the file.
+        let mut malformed = bytes[..6].to_vec();
+        malformed.extend_from_slice(&[1, 0x7f, 0]);
+        malformed.extend_from_slice(&crc32(&malformed).to_le_bytes());
+        assert!(matches!(TemporalGraph::<usize, u64>::load_from(malformed.as_slice()), Err(PersistError::Corrupt(_))));
+    }
+
+    /// Wraps the payloads of a nodes and an edges section into a file of
+    /// `usize` nodes and `u64` times with a valid checksum.
+    fn file_with(nodes: &[u8], edges: &[u8]) -> Vec<u8> {
+        let mut bytes = b"CHRG".to_vec();
+        bytes.extend_from_slice(&[FORMAT_MAJOR, 0, 1, 2, 0, 0, 2, nodes.len() as u8]);
+        bytes.extend_from_slice(nodes);
+        bytes.extend_from_slice(&[3, edges.len() as u8]);
+        bytes.extend_from_slice(edges);
+        bytes.push(0);
+        let checksum = crc32(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_rejects_invalid_contents() {
+        let load = |bytes: Vec<u8>| TemporalGraph::<usize, u64>::load_from(bytes.as_slice());
+        let corrupt = |bytes: Vec<u8>| match load(bytes) {
+            Err(PersistError::Corrupt(reason)) => reason,
+            other => panic!("expected a corrupt file, got {:?}", other),
+        };
+        let two_nodes = [2, 1, 0, 2, 0];
+        // One edge from node 0 to node 1 of the table, over `[5, end)`.
+        let edge = |end: u8| {
+            let mut edges = vec![1, 0, 1, 1, 5, end];
+            edges.extend_from_slice(&1.0f64.to_le_bytes());
+            edges.push(0);
+            edges
+        };
+
+        let g = load(file_with(&two_nodes, &edge(6))).unwrap();
+        assert_eq!(g.first_contact(&1, &2), Some(5));
+        assert!(corrupt(file_with(&two_nodes, &edge(5))).contains("empty"));
+        assert!(corrupt(file_with(&two_nodes, &edge(4))).contains("empty"));
+        let mut stray = edge(6);
+        stray[2] = 2;
+        assert!(corrupt(file_with(&two_nodes, &stray)).contains("past the 2 nodes"));
+        assert!(corrupt(file_with(&[1, 1, 3, 5, 5], &[0])).contains("before it is born"));
+        assert!(load(file_with(&[1, 1, 3, 5, 6], &[0])).is_ok());
+
+        // Signed times are zigzag-encoded: an edge from `5` lasting `-1`.
+        let mut negative = vec![1, 0, 1, 2, 10, 1];
+        negative.extend_from_slice(&1.0f64.to_le_bytes());
+        negative.push(0);
+        match TemporalGraph::<usize, i64>::load_from(file_with(&two_nodes, &negative).as_slice()) {
+            Err(PersistError::Corrupt(reason)) => assert!(reason.contains("negative")),
+            other => panic!("expected a corrupt file, got {:?}", other),
+        }
+        negative[5] = 2;
+        let g = TemporalGraph::<usize, i64>::load_from(file_with(&two_nodes, &negative).as_slice()).unwrap();
+        assert_eq!(g.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_skips_sections_from_newer_minor_versions() {
+        let g = build();
+        let mut bytes = Vec::new();
+        g.save_to(&mut bytes).unwrap();
+
+        // Insert an unknown section before the end marker and bump the minor version.
+        let mut newer = bytes[..bytes.len() - 5].to_vec();
+        newer[5] += 1;
+        newer.extend_from_slice(&[42, 3, 1, 2, 3, 0]);
+        newer.extend_from_slice(&crc32(&newer).to_le_bytes());
+        assert_same(&g, &TemporalGraph::load_from(newer.as_slice()).unwrap());
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
     }
 }
 
-mod cg_unit_tests {
-    use super::chrono::*;
-    use std::time::Instant;
+#[cfg(test)]
+mod wal_tests {
+    use std::fs::{self, File, OpenOptions};
+    use std::io::{self, Write};
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
+    use super::chrono::ChronoGraph;
+    use super::edge::Interval;
+    use super::error::{GraphError, PersistError, WalError};
+    use super::ops::TemporalGraphOps;
+    use super::sequential::TemporalGraph;
+    use super::wal::{DurableGraph, LogFile};
+
+    /// Returns an empty scratch directory for one test.
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("chronograph-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Failures for [`FaultyLog`] to inject, each used once.
+    #[derive(Debug, Default)]
+    struct Faults {
+        /// Write this many bytes of the next write, then fail.
+        short_write: Option<usize>,
+        fail_sync: bool,
+        fail_truncate: bool,
+    }
+
+    /// The real log file, failing on demand.
+    #[derive(Debug)]
+    struct FaultyLog {
+        file: File,
+        faults: Arc<Mutex<Faults>>,
+    }
+
+    impl FaultyLog {
+        fn install(g: &mut DurableGraph<TemporalGraph>, dir: &Path) -> Arc<Mutex<Faults>> {
+            let faults = Arc::new(Mutex::new(Faults::default()));
+            let file = OpenOptions::new().append(true).open(dir.join("wal.log")).unwrap();
+            g.set_log_file(Box::new(FaultyLog { file, faults: faults.clone() }));
+            faults
+        }
+    }
+
+    impl Write for FaultyLog {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if let Some(len) = self.faults.lock().unwrap().short_write.take() {
+                self.file.write_all(&buf[..len.min(buf.len())])?;
+                return Err(io::Error::other("disk full"));
+            }
+            self.file.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.file.flush()
+        }
+    }
+
+    impl LogFile for FaultyLog {
+        fn sync_data(&self) -> io::Result<()> {
+            if std::mem::take(&mut self.faults.lock().unwrap().fail_sync) {
+                return Err(io::Error::other("sync failed"));
+            }
+            self.file.sync_data()
+        }
+
+        fn set_len(&self, len: u64) -> io::Result<()> {
+            if std::mem::take(&mut self.faults.lock().unwrap().fail_truncate) {
+                return Err(io::Error::other("truncate failed"));
+            }
+            self.file.set_len(len)
+        }
+    }
 
     #[test]
-    fn test_add_node() {
-        let mut cg = ChronoGraph::new();
-        cg.add_node(1);
-        assert!(cg.get_nodes().contains(&1));
+    fn test_replays_log_after_crash() {
+        let dir = scratch_dir("wal-replay");
+        {
+            let mut g: DurableGraph<TemporalGraph> = DurableGraph::open(&dir).unwrap();
+            (1..=3).for_each(|i| g.add_node(i).unwrap());
+            g.add_node_at(4, 10).unwrap();
+            g.add_edge(1, 2, 5).unwrap();
+            g.add_edge_interval(2, 3, 1, 4).unwrap();
+            g.add_edge_with_duration(3, 4, Interval::starting_at(12), 2).unwrap();
+            g.remove_edge(1, 2, 5).unwrap();
+            assert!(matches!(g.add_edge(1, 9, 0), Err(WalError::Graph(GraphError::MissingDestination(9)))));
+            g.remove_node_at(3, 20).unwrap();
+            assert_eq!(g.lsn(), 10);
+            // Dropped without a checkpoint, as after a crash.
+        }
+        let g: DurableGraph<TemporalGraph> = DurableGraph::open(&dir).unwrap();
+        assert_eq!(g.lsn(), 10);
+        assert_eq!(g.checkpoint_lsn(), 0);
+        let graph = g.graph();
+        assert_eq!(graph.get_nodes().len(), 4);
+        assert!(graph.get_neighbors_at(1, 6).is_empty());
+        assert_eq!(graph.get_neighbors_at(2, 2), vec![3]);
+        assert_eq!(graph.edges_of(&3)[0].duration, Some(2));
+        assert_eq!(graph.node_lifetime(&3).unwrap().died, Some(20));
+        fs::remove_dir_all(&dir).unwrap();
+    }
 
-        eprintln!("\nCG for test_add_node:");
-        cg.print();
+    #[test]
+    fn test_checkpoint_and_torn_tail() {
+        let dir = scratch_dir("wal-torn");
+        {
+            let mut g: DurableGraph<TemporalGraph> = DurableGraph::open(&dir).unwrap();
+            g.set_checkpoint_interval(Some(3));
+            (1..=4).for_each(|i| g.add_node(i).unwrap());
+            g.add_edge(1, 2, 1).unwrap();
+            assert_eq!(g.checkpoint_lsn(), 3);
+        }
+        // A crash halfway through appending the next record.
+        let torn = [9, 0, 0, 0, 0xde, 0xad, 0xbe, 0xef, 6, 0];
+        OpenOptions::new().append(true).open(dir.join("wal.log")).unwrap().write_all(&torn).unwrap();
+        let log_len = fs::metadata(dir.join("wal.log")).unwrap().len();
+
+        let mut g: DurableGraph<TemporalGraph> = DurableGraph::open(&dir).unwrap();
+        assert_eq!(g.lsn(), 5);
+        assert_eq!(g.graph().get_nodes().len(), 4);
+        assert_eq!(g.graph().get_neighbors_at(1, 1), vec![2]);
+        assert_eq!(fs::metadata(dir.join("wal.log")).unwrap().len(), log_len - torn.len() as u64);
+
+        g.add_edge(3, 4, 2).unwrap();
+        drop(g);
+        let g: DurableGraph<TemporalGraph> = DurableGraph::open(&dir).unwrap();
+        assert_eq!(g.lsn(), 6);
+        assert_eq!(g.into_inner().edge_count(), 2);
+        fs::remove_dir_all(&dir).unwrap();
     }
 
     #[test]
-    fn test_add_edge_success() {
-        let mut cg = ChronoGraph::new();
-        cg.add_node(1);
-        cg.add_node(2);
-        let result = cg.add_edge(1, 2, 5);
-        assert!(result.is_ok());
-        assert_eq!(cg.get_edges().get(&1).unwrap(), &vec![(2, 5)]);
+    fn test_stale_log_is_not_applied_twice() {
+        let dir = scratch_dir("wal-stale");
+        let mut g = DurableGraph::open_with(&dir, ChronoGraph::<usize, u64>::new_undirected).unwrap();
+        g.add_node(1).unwrap();
+        g.add_node(2).unwrap();
+        g.add_edge(1, 2, 3).unwrap();
+        let stale = fs::read(dir.join("wal.log")).unwrap();
+        g.checkpoint().unwrap();
+        g.add_edge(2, 1, 7).unwrap();
+        drop(g);
+
+        // A crash after the checkpoint was written but before the log was replaced.
+        fs::write(dir.join("wal.log"), &stale).unwrap();
+        let g = DurableGraph::open_with(&dir, ChronoGraph::<usize, u64>::new).unwrap();
+        assert!(!g.graph().is_directed());
+        assert_eq!(g.lsn(), 3);
+        assert_eq!(g.graph().edge_count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
 
-        eprintln!("\nCG for test_add_edge_success:");
-        cg.print();
+    #[test]
+    fn test_failed_appends_are_cut_off() {
+        let dir = scratch_dir("wal-faults");
+        let mut g: DurableGraph<TemporalGraph> = DurableGraph::open(&dir).unwrap();
+        let faults = FaultyLog::install(&mut g, &dir);
+        g.add_node(1).unwrap();
+
+        faults.lock().unwrap().short_write = Some(7);
+        assert!(matches!(g.add_node(2), Err(WalError::Persist(PersistError::Io(_)))));
+        faults.lock().unwrap().fail_sync = true;
+        assert!(g.add_node(3).is_err());
+        assert_eq!(g.lsn(), 1);
+        assert!(!g.graph().contains_node(&3));
+
+        g.add_node(4).unwrap();
+        g.add_node(5).unwrap();
+        assert!(!g.is_poisoned());
+        drop(g);
+
+        // Neither failed record is replayed, and the later ones are not lost.
+        let g: DurableGraph<TemporalGraph> = DurableGraph::open(&dir).unwrap();
+        assert_eq!(g.lsn(), 3);
+        let mut nodes: Vec<_> = g.graph().get_nodes().iter().copied().collect();
+        nodes.sort();
+        assert_eq!(nodes, vec![1, 4, 5]);
+        fs::remove_dir_all(&dir).unwrap();
     }
 
     #[test]
-    fn test_add_edge_missing_node() {
-        let mut cg = ChronoGraph::new();
-        cg.add_node(1);
-        let result = cg.add_edge(1, 2, 5);
-        assert!(result.is_err());
+    fn test_unrepairable_log_poisons_the_graph() {
+        let dir = scratch_dir("wal-poisoned");
+        let mut g: DurableGraph<TemporalGraph> = DurableGraph::open(&dir).unwrap();
+        let faults = FaultyLog::install(&mut g, &dir);
+        g.add_node(1).unwrap();
+
+        *faults.lock().unwrap() = Faults { short_write: Some(3), fail_sync: false, fail_truncate: true };
+        assert!(g.add_node(2).is_err());
+        assert!(g.is_poisoned());
+        assert!(g.add_node(3).is_err());
+        assert!(g.checkpoint().is_err());
+        drop(g);
+
+        // Reopening cuts the torn record off.
+        let mut g: DurableGraph<TemporalGraph> = DurableGraph::open(&dir).unwrap();
+        assert_eq!(g.lsn(), 1);
+        g.add_node(6).unwrap();
+        drop(g);
+        assert_eq!(DurableGraph::<TemporalGraph>::open(&dir).unwrap().graph().get_nodes().len(), 2);
+        fs::remove_dir_all(&dir).unwrap();
     }
 
     #[test]
-    fn test_get_neighbors_at() {
-        let mut cg = ChronoGraph::new();
-        cg.add_node(1);
-        cg.add_node(2);
-        cg.add_node(3);
-        cg.add_edge(1, 2, 5).unwrap();
-        cg.add_edge(1, 3, 15).unwrap();
-        let neighbors_at_10 = cg.get_neighbors_at(1, 10);
-        assert_eq!(neighbors_at_10, vec![2]);
-        let neighbors_at_20 = cg.get_neighbors_at(1, 20);
-        assert_eq!(neighbors_at_20.len(), 2);
-        assert!(neighbors_at_20.contains(&2));
-        assert!(neighbors_at_20.contains(&3));
+    fn test_failed_auto_checkpoint_keeps_the_mutation() {
+        let dir = scratch_dir("wal-checkpoint-fails");
+        let mut g: DurableGraph<TemporalGraph> = DurableGraph::open(&dir).unwrap();
+        g.set_checkpoint_interval(Some(1));
+        // A directory in the way of the temporary checkpoint file.
+        let blocker = dir.join("checkpoint.bin.tmp");
+        fs::create_dir(&blocker).unwrap();
+
+        g.add_node(1).unwrap();
+        assert!(matches!(g.take_checkpoint_error(), Some(PersistError::Io(_))));
+        assert!(g.take_checkpoint_error().is_none());
+        assert!(matches!(g.add_edge(1, 9, 0), Err(WalError::Graph(GraphError::MissingDestination(9)))));
+        assert!(g.take_checkpoint_error().is_some());
+        assert_eq!(g.checkpoint_lsn(), 0);
+        assert!(!g.is_poisoned());
+
+        fs::remove_dir(&blocker).unwrap();
+        g.add_node(2).unwrap();
+        assert!(g.take_checkpoint_error().is_none());
+        assert_eq!(g.checkpoint_lsn(), 3);
+        drop(g);
+        assert_eq!(DurableGraph::<TemporalGraph>::open(&dir).unwrap().graph().get_nodes().len(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
 
-        eprintln!("\nCG for test_get_neighbors_at:");
-        cg.print();
+    #[test]
+    fn test_rejects_damaged_checkpoint() {
+        let dir = scratch_dir("wal-damaged");
+        let mut g: DurableGraph<TemporalGraph> = DurableGraph::open(&dir).unwrap();
+        g.add_node(1).unwrap();
+        g.checkpoint().unwrap();
+        drop(g);
+
+        let mut bytes = fs::read(dir.join("checkpoint.bin")).unwrap();
+        bytes[5] ^= 1;
+        fs::write(dir.join("checkpoint.bin"), &bytes).unwrap();
+        let err = DurableGraph::<TemporalGraph>::open(&dir).unwrap_err();
+        assert!(matches!(err, PersistError::ChecksumMismatch { .. }));
+        fs::remove_dir_all(&dir).unwrap();
     }
+}
+
+#[cfg(test)]
+mod import_tests {
+    use super::chrono::ChronoGraph;
+    use super::edge::{Interval, PropertyValue};
+    use super::import::{ImportError, ImportOptions, OnError};
+    use super::ops::TemporalGraphOps;
+    use super::sequential::TemporalGraph;
 
     #[test]
-    fn test_get_neighbors_no_edge() {
-        let mut cg = ChronoGraph::new();
-        cg.add_node(1);
-        let neighbors = cg.get_neighbors_at(1, 10);
-        assert!(neighbors.is_empty());
+    fn test_import_snap() {
+        let input = "# Directed temporal graph\n# FromNodeId ToNodeId Timestamp\n1 2 10\n\n2\t3   12\n3 1 15\n";
+        let mut g: TemporalGraph = TemporalGraph::new();
+        let report = g.import_edges(input.as_bytes(), &ImportOptions::snap()).unwrap();
+        assert_eq!(report.lines, 6);
+        assert_eq!(report.edges_added, 3);
+        assert_eq!(report.nodes_added, 3);
+        assert!(report.skipped.is_empty());
+        assert_eq!(g.get_neighbors_at(2, 12), vec![3]);
+        assert_eq!(g.get_neighbors_at(3, 15), vec![1]);
+    }
 
-        eprintln!("\nCG for test_get_neighbors_no_edge:");
-        cg.print();
+    #[test]
+    fn test_import_csv_with_named_columns() {
+        let input = "when,until,from,to,weight,kind\n5,9,alice,bob,2.5,call\n7,8,bob,carol,1,\n";
+        let options = ImportOptions::csv()
+            .with_header()
+            .with_columns("from", "to", "when")
+            .with_end_column("until")
+            .with_weight_column("weight")
+            .with_property("kind", "kind");
+        let mut g: TemporalGraph<String, i64> = TemporalGraph::new_undirected();
+        g.import_edges(input.as_bytes(), &options).unwrap();
+        let edge = &g.edges_of(&"alice".to_string())[0];
+        assert_eq!(edge.interval, Interval::new(5, 9));
+        assert_eq!(edge.weight, 2.5);
+        assert_eq!(edge.properties.get("kind"), Some(&PropertyValue::Str("call".to_string())));
+        assert!(g.edges_of(&"carol".to_string())[0].properties.is_empty());
+        assert_eq!(g.get_neighbors_at("carol".to_string(), 7), vec!["bob".to_string()]);
     }
 
     #[test]
-    fn test_get_neighbors_nonexistent_node() {
-        let cg = ChronoGraph::new();
-        let neighbors = cg.get_neighbors_at(999, 100);
-        assert!(neighbors.is_empty());
+    fn test_import_reports_malformed_lines() {
+        let input = "1 2 3\n1 x 4\n2 3\n3 1 5\n";
+        let mut g: TemporalGraph = TemporalGraph::new();
+        let Err(ImportError::Malformed(err)) = g.import_edges(input.as_bytes(), &ImportOptions::snap()) else {
+            panic!("expected a malformed line");
+        };
+        assert_eq!(err.line, 2);
+        assert!(err.to_string().starts_with("line 2: "));
+
+        let mut g: TemporalGraph = TemporalGraph::new();
+        let options = ImportOptions::snap().on_error(OnError::Skip);
+        let report = g.import_edges(input.as_bytes(), &options).unwrap();
+        assert_eq!(report.edges_added, 2);
+        assert_eq!(report.skipped.iter().map(|err| err.line).collect::<Vec<_>>(), vec![2, 3]);
+
+        let options = ImportOptions::csv().with_columns("src", 1, 2);
+        let err = TemporalGraph::<usize, u64>::new().import_edges("1,2,3\n".as_bytes(), &options).unwrap_err();
+        assert!(matches!(err, ImportError::UnknownColumn(name) if name == "src"));
     }
 
     #[test]
-    fn test_remove_node() {
-        let mut cg = ChronoGraph::new();
-        cg.add_node(1);
-        cg.add_node(2);
-        cg.add_node(3);
-        cg.add_edge(1, 2, 5).unwrap();
-        cg.add_edge(3, 2, 10).unwrap();
+    fn test_parallel_import_matches_sequential() {
+        let mut input = String::from("src\tdst\ttime\n");
+        for i in 0..500u64 {
+            input.push_str(&format!("{}\t{}\t{}\n", i % 37, (i * 7) % 41, i));
+        }
+        input.push_str("oops\n");
+        let options = ImportOptions::tsv().with_header().with_chunk_size(64).on_error(OnError::Skip);
+
+        let mut tg: TemporalGraph = TemporalGraph::new();
+        let expected = tg.import_edges(input.as_bytes(), &options).unwrap();
+        let mut cg = ChronoGraph::new_with_threads(4).unwrap();
+        let report = cg.import_edges(input.as_bytes(), &options).unwrap();
+        assert_eq!(report, expected);
+        assert_eq!(report.skipped[0].line, 502);
+        assert_eq!(cg.get_nodes(), tg.get_nodes());
+        for node in tg.get_nodes() {
+            assert_eq!(cg.edges_of(node), tg.edges_of(node));
+        }
+    }
+}
 
-        assert_eq!(cg.remove_node(2), Ok(2));
-        assert!(!cg.get_nodes().contains(&2));
-        assert!(cg.get_edges().get(&1).unwrap().is_empty());
-        assert!(cg.get_edges().get(&3).unwrap().is_empty());
+#[cfg(test)]
+mod export_tests {
+    use super::chrono::ChronoGraph;
+    use super::edge::{Interval, Properties};
+    use super::ops::TemporalGraphOps;
+    use super::sequential::TemporalGraph;
+
+    fn build<G: TemporalGraphOps<Node = usize, Time = u64>>(mut g: G) -> G {
+        (1..=3).for_each(|i| g.add_node(i));
+        g.add_node_at(4, 10);
+        g.remove_node_at(3, 20).unwrap();
+        let mut properties = Properties::new();
+        properties.insert("kind".to_string(), "a<b".into());
+        g.add_edge_with(1, 2, Interval::new(2, 6), 1.5, properties).unwrap();
+        g.add_edge_interval(1, 2, 8, 12).unwrap();
+        g.add_edge(2, 3, 4).unwrap();
+        g.add_edge(3, 4, 15).unwrap();
+        g
+    }
 
-        eprintln!("\nCG for test_remove_node:");
-        cg.print();
+    fn export<F: Fn(&mut Vec<u8>)>(write: F) -> String {
+        let mut out = Vec::new();
+        write(&mut out);
+        String::from_utf8(out).unwrap()
     }
 
     #[test]
-    fn test_remove_node_nonexistent() {
-        let mut cg = ChronoGraph::new();
-        assert_eq!(cg.remove_node(42), Err(false));
+    fn test_dot_export() {
+        let g = build(TemporalGraph::new());
+        let dot = export(|out| g.write_dot(out, None).unwrap());
+        assert!(dot.starts_with("digraph {\n  \"1\";\n"));
+        assert!(dot.contains("  \"3\" [\"died\"=\"20\"];\n"));
+        assert!(dot.contains(r#"  "1" -> "2" ["label"="[2, 6)", "start"="2", "end"="6", "weight"="1.5", "kind"="a<b"];"#));
+        assert_eq!(dot.matches(" -> ").count(), 4);
+
+        let window = export(|out| g.write_dot(out, Some((0, 5))).unwrap());
+        assert_eq!(window.matches(" -> ").count(), 2);
+        assert!(!window.contains("  \"4\" [\"born\""));
+
+        let u = build(TemporalGraph::new_undirected());
+        let dot = export(|out| u.write_dot(out, None).unwrap());
+        assert!(dot.starts_with("graph {"));
+        assert_eq!(dot.matches(" -- ").count(), 4);
     }
 
     #[test]
-    fn test_remove_edge() {
-        let mut cg = ChronoGraph::new();
-        cg.add_node(1);
-        cg.add_node(2);
-        cg.add_edge(1, 2, 5).unwrap();
+    fn test_graphml_export() {
+        let g = build(TemporalGraph::new());
+        let xml = export(|out| g.write_graphml(out, None).unwrap());
+        assert!(xml.contains(r#"<key id="start" for="edge" attr.name="start" attr.type="long"/>"#));
+        assert!(xml.contains(r#"<key id="p0" for="edge" attr.name="kind" attr.type="string"/>"#));
+        assert!(xml.contains(r#"<graph id="G" edgedefault="directed">"#));
+        assert!(xml.contains(r#"<node id="4"><data key="born">10</data></node>"#));
+        assert!(xml.contains(r#"<edge id="e0" source="1" target="2"><data key="start">2</data><data key="end">6</data><data key="weight">1.5</data><data key="p0">a&lt;b</data></edge>"#));
+        assert_eq!(xml.matches("<edge ").count(), 4);
+        assert!(xml.trim_end().ends_with("</graphml>"));
+    }
 
-        assert_eq!(cg.remove_edge(1, 2, 5), Ok((1, 2, 5)));
-        assert!(cg.get_edges().get(&1).unwrap().is_empty());
+    #[test]
+    fn test_gexf_export() {
+        let g = build(TemporalGraph::new());
+        let xml = export(|out| g.write_gexf(out, None).unwrap());
+        assert!(xml.contains(r#"<graph mode="dynamic" defaultedgetype="directed" timerepresentation="interval">"#));
+        assert!(xml.contains(r#"<node id="3" label="3" endopen="20"/>"#));
+        // Both 1 -> 2 edges share one element with a spell each.
+        assert_eq!(xml.matches("<edge ").count(), 3);
+        assert!(xml.contains("<spell start=\"2\" endopen=\"6\"/>\n          <spell start=\"8\" endopen=\"12\"/>"));
+        assert!(xml.contains(r#"<attvalue for="p0" value="a&lt;b" start="2" endopen="6"/>"#));
+        assert!(xml.contains(r#"<spell start="15"/>"#));
+
+        let cg = build(ChronoGraph::new());
+        assert_eq!(export(|out| cg.write_gexf(out, None).unwrap()), xml);
+        assert_eq!(
+            export(|out| cg.write_graphml(out, Some((0, 5))).unwrap()),
+            export(|out| g.write_graphml(out, Some((0, 5))).unwrap())
+        );
+    }
 
-        eprintln!("\nCG for test_remove_edge:");
-        cg.print();
+    #[test]
+    fn test_gexf_splits_overlapping_parallel_edges() {
+        let mut g = build(TemporalGraph::new());
+        g.add_edge_interval(1, 2, 4, 9).unwrap();
+        g.add_edge(1, 2, 20).unwrap();
+        let xml = export(|out| g.write_gexf(out, None).unwrap());
+        // [4, 9) overlaps both earlier spells, so it gets an element of its
+        // own; the edge from 20 follows [8, 12) in the first one.
+        assert_eq!(xml.matches("<edge ").count(), 4);
+        assert_eq!(xml.matches(r#"source="1" target="2""#).count(), 2);
+        assert!(xml.contains(
+            "<spell start=\"2\" endopen=\"6\"/>\n          <spell start=\"8\" endopen=\"12\"/>\n          <spell start=\"20\"/>\n        </spells>"
+        ));
+        assert!(xml.contains("<spells>\n          <spell start=\"4\" endopen=\"9\"/>\n        </spells>"));
+        assert!(xml.contains(r#"<attvalue for="weight" value="1" start="4" endopen="9"/>"#));
+    }
+}
+
+#[cfg(test)]
+mod events_tests {
+    use super::chrono::ChronoGraph;
+    use super::edge::{Interval, Properties};
+    use super::import::{ImportError, OnError};
+    use super::ops::TemporalGraphOps;
+    use super::sequential::TemporalGraph;
+
+    /// Compares every node, lifetime and edge of two graphs.
+    fn assert_same<G: TemporalGraphOps, H: TemporalGraphOps<Node = G::Node, Time = G::Time>>(a: &G, b: &H) {
+        assert_eq!(a.get_nodes(), b.get_nodes());
+        assert_eq!(a.directionality(), b.directionality());
+        for node in a.get_nodes() {
+            assert_eq!(a.node_lifetime(node), b.node_lifetime(node));
+            assert_eq!(a.edges_of(node), b.edges_of(node));
+        }
+    }
+
+    fn write(write: impl Fn(&mut Vec<u8>)) -> String {
+        let mut out = Vec::new();
+        write(&mut out);
+        String::from_utf8(out).unwrap()
     }
 
     #[test]
-    fn test_remove_edge_nonexistent() {
-        let mut cg = ChronoGraph::new();
-        cg.add_node(1);
-        cg.add_node(2);
-        cg.add_edge(1, 2, 5).unwrap();
+    fn test_event_round_trip() {
+        let mut g = TemporalGraph::new();
+        (1..=3).for_each(|i| g.add_node(i));
+        g.add_node_at(4, 5);
+        g.remove_node_at(3, 40).unwrap();
+        let mut properties = Properties::new();
+        properties.insert("kind".to_string(), "say \"hi\"\n☎".into());
+        properties.insert("count".to_string(), (-7i64).into());
+        properties.insert("score".to_string(), 2.0.into());
+        properties.insert("flagged".to_string(), true.into());
+        g.add_edge_with(1, 2, Interval::new(3, 9), 2.5, properties).unwrap();
+        g.add_edge(1, 3, 1).unwrap();
+        g.add_edge_with_duration(2, 4, Interval::starting_at(6), 3).unwrap();
+        g.add_edge(4, 4, 7).unwrap();
+
+        let stream = write(|out| g.write_events(out).unwrap());
+        assert_eq!(stream.lines().count(), 9);
+        assert!(stream.starts_with("{\"op\":\"add_node\",\"node\":1}\n"));
+        assert!(stream.contains(r#"{"op":"add_edge","src":2,"dst":4,"ts":6,"duration":3}"#));
+        assert!(stream.ends_with("{\"op\":\"remove_node\",\"node\":3,\"ts\":40}\n"));
+
+        let mut loaded = TemporalGraph::new();
+        let report = loaded.read_events(stream.as_bytes(), OnError::Fail).unwrap();
+        assert_eq!(report.applied, 9);
+        assert_same(&g, &loaded);
+        let mut parallel = ChronoGraph::new();
+        parallel.read_events(stream.as_bytes(), OnError::Fail).unwrap();
+        assert_same(&g, &parallel);
+        assert_eq!(write(|out| parallel.write_events(out).unwrap()), stream);
+    }
 
-        assert_eq!(cg.remove_edge(1, 2, 99), Err(false));
-        assert_eq!(cg.remove_edge(2, 1, 5), Err(false));
-        assert_eq!(cg.remove_edge(1, 3, 5), Err(false));
+    #[test]
+    fn test_undirected_string_keys() {
+        let mut g: TemporalGraph<String, i64> = TemporalGraph::new_undirected();
+        for key in ["007", "a b", "1.5", "-3"] {
+            g.add_node_at(key.to_string(), -10);
+        }
+        g.add_edge_interval("007".to_string(), "a b".to_string(), -5, 5).unwrap();
+        g.add_edge("1.5".to_string(), "-3".to_string(), -1).unwrap();
+
+        let stream = write(|out| g.write_events(out).unwrap());
+        assert!(stream.contains(r#"{"op":"add_node","node":"007","ts":-10}"#));
+        assert!(stream.contains(r#"{"op":"add_edge","src":1.5,"dst":-3,"ts":-1}"#));
+        assert_eq!(stream.matches("add_edge").count(), 2);
+
+        let mut loaded: TemporalGraph<String, i64> = TemporalGraph::new_undirected();
+        loaded.read_events(stream.as_bytes(), OnError::Fail).unwrap();
+        assert_same(&g, &loaded);
+    }
+
+    #[test]
+    fn test_event_errors_report_lines() {
+        let stream = [
+            r#"{"op":"add_node","node":1}"#,
+            r#"{"op":"add_node","node":2, "extra":[1, {"a":null}]}"#,
+            r#"{"op":"add_edge","src":1,"dst":2"#,
+            "",
+            r#"{"op":"rename","node":1}"#,
+            r#"{"op":"add_edge","src":1,"dst":3,"ts":4}"#,
+            r#"{"op":"add_edge","src":1,"dst":2,"ts":"soon"}"#,
+            r#"{"op":"add_edge","src":1,"dst":2,"ts":4,"duration":1,"weight":2}"#,
+            r#"{"op":"add_edge","src":1,"dst":2,"ts":4,"end":8}"#,
+            r#"{"op":"remove_edge","src":1,"dst":2,"ts":4}"#,
+            r#"{"op":"remove_node","node":2,"ts":9}"#,
+        ]
+        .join("\n");
+
+        let mut g: TemporalGraph = TemporalGraph::new();
+        let Err(ImportError::Malformed(err)) = g.read_events(stream.as_bytes(), OnError::Fail) else {
+            panic!("expected a malformed line");
+        };
+        assert_eq!(err.line, 3);
+        assert_eq!(g.get_nodes().len(), 2);
+
+        let mut g: TemporalGraph = TemporalGraph::new();
+        let report = g.read_events(stream.as_bytes(), OnError::Skip).unwrap();
+        assert_eq!(report.lines, 11);
+        assert_eq!(report.applied, 5);
+        assert_eq!(report.skipped.iter().map(|err| err.line).collect::<Vec<_>>(), vec![3, 5, 6, 7, 8]);
+        assert!(report.skipped[1].message.contains("unknown op"));
+        assert!(g.get_neighbors_at(1, 5).is_empty());
+        assert_eq!(g.node_lifetime(&2).unwrap().died, Some(9));
+    }
 
-        eprintln!("\nCG for test_remove_edge_nonexistent:");
-        cg.print();
+    #[test]
+    fn test_json_strings() {
+        let stream = r#"{ "node" : "café 😀\t\"x\"" , "op" : "add_node" }"#;
+        let mut g: TemporalGraph<String, u64> = TemporalGraph::new();
+        g.read_events(stream.as_bytes(), OnError::Fail).unwrap();
+        assert!(g.contains_node(&"café 😀\t\"x\"".to_string()));
+
+        let stream = write(|out| g.write_events(out).unwrap());
+        assert_eq!(stream, "{\"op\":\"add_node\",\"node\":\"café 😀\\t\\\"x\\\"\"}\n");
+        for bad in [r#"{"op":"add_node","node":"\ud83d"}"#, r#"{"op":"add_node","node":01}"#, r#"["add_node"]"#] {
+            assert!(TemporalGraph::<String, u64>::new().read_events(bad.as_bytes(), OnError::Fail).is_err());
+        }
+
+        // Deep nesting is an error on its line, not a stack overflow.
+        let deep = format!("{}\n{{\"op\":\"add_node\",\"node\":\"x\",\"extra\":{}{}}}\n", "[".repeat(200_000), "[".repeat(127), "]".repeat(127));
+        let report = g.read_events(deep.as_bytes(), OnError::Skip).unwrap();
+        assert_eq!(report.applied, 1);
+        assert_eq!(report.skipped.len(), 1);
+        assert!(report.skipped[0].message.starts_with("nesting too deep"));
     }
 }
 
+#[cfg(test)]
+mod generic_type_tests {
+    use super::edge::Interval;
+    use super::error::GraphError;
+    use super::ops::TemporalGraphOps;
+    use super::paths::earliest_arrival;
+    use super::sequential::TemporalGraph;
+    use super::chrono::ChronoGraph;
+
+    fn check_string_ids_signed_time<G: TemporalGraphOps<Node = String, Time = i64> + Default>() {
+        let mut g = G::default();
+        g.add_node("alice".to_string());
+        g.add_node("bob".to_string());
+        g.add_edge_interval("alice".to_string(), "bob".to_string(), -500, -100).unwrap();
+
+        assert!(g.get_neighbors_at("alice".to_string(), -501).is_empty());
+        assert_eq!(g.get_neighbors_at("alice".to_string(), -300), vec!["bob".to_string()]);
+        assert!(g.get_neighbors_at("alice".to_string(), 0).is_empty());
+        assert_eq!(
+            g.add_edge("alice".to_string(), "carol".to_string(), 0),
+            Err(GraphError::MissingDestination("carol".to_string()))
+        );
+        assert_eq!(
+            g.add_edge_with_duration("alice".to_string(), "bob".to_string(), Interval::starting_at(-50), -1),
+            Err(GraphError::InvalidDuration(-1))
+        );
+        g.add_edge_with_duration("bob".to_string(), "alice".to_string(), Interval::starting_at(-50), 20).unwrap();
+        let arrivals = earliest_arrival(&g, "alice".to_string(), -400);
+        assert_eq!(arrivals.arrival_time(&"bob".to_string()), Some(-400));
+        assert_eq!(arrivals.arrival_time(&"alice".to_string()), Some(-400));
+        assert_eq!(earliest_arrival(&g, "bob".to_string(), -400).arrival_time(&"alice".to_string()), Some(-30));
+    }
+
+    #[test]
+    fn test_tg_string_ids_signed_time() {
+        check_string_ids_signed_time::<TemporalGraph<String, i64>>();
+    }
+
+    #[test]
+    fn test_cg_string_ids_signed_time() {
+        check_string_ids_signed_time::<ChronoGraph<String, i64>>();
+    }
+}
+
+#[cfg(test)]
 mod benchmark_test_1 {
     use super::sequential::*;
     use super::chrono::*;
+    use super::ops::TemporalGraphOps;
     use rayon::prelude::*;
     use std::mem::size_of_val;
     use std::time::Instant;
@@ -357,16 +2223,32 @@ mod benchmark_test_1 {
     }
 }
 
+#[cfg(test)]
 mod benchmark_test_2 {
     use super::sequential::*;
     use super::chrono::*;
+    use super::ops::TemporalGraphOps;
     use rayon::prelude::*;
     use std::mem::size_of_val;
-    use std::time::Instant;
+    use std::time::{Duration, Instant};
 
     const NODE_COUNT: usize = 10_000;
-    const EDGE_PER_NODE: usize = 10;
     const QUERY_TIMESTAMP: u64 = 50;
+
+    /// The neighbor query without the sorted adjacency lists: a scan over
+    /// every edge of `node`.
+    fn linear_scan<G: TemporalGraphOps<Node = usize, Time = u64>>(graph: &G, node: usize, timestamp: u64) -> Vec<usize> {
+        graph
+            .edges_of(&node)
+            .iter()
+            .filter(|edge| edge.is_active_at(timestamp))
+            .map(|edge| edge.dst)
+            .collect()
+    }
+
+    fn speedup(scan: Duration, lookup: Duration) -> f64 {
+        scan.as_secs_f64() / lookup.as_secs_f64().max(f64::MIN_POSITIVE)
+    }
     #[test]
     fn benchmark_tg_dense() {
         let mut tg = TemporalGraph::new();
@@ -394,6 +2276,19 @@ mod benchmark_test_2 {
         let query_throughput = NODE_COUNT as f64 / duration_query.as_secs_f64();
         println!("[TG Dense] Total neighbors: {}, throughput: {:.2} queries/sec", total_neighbors, query_throughput);
 
+        let start_scan = Instant::now();
+        let scanned: usize = (0..NODE_COUNT).map(|i| linear_scan(&tg, i, QUERY_TIMESTAMP).len()).sum();
+        let duration_scan = start_scan.elapsed();
+        println!(
+            "[TG Dense] Linear scan: {:?}, sorted lookup: {:?} ({:.1}x faster)",
+            duration_scan,
+            duration_query,
+            speedup(duration_scan, duration_query)
+        );
+        // Edges start every 5 ticks, so 11 of each node's edges have started by 50.
+        assert_eq!(total_neighbors, 11 * NODE_COUNT);
+        assert_eq!(scanned, total_neighbors);
+
         println!("[TG Dense] Memory: {} bytes", size_of_val(&tg));
     }
 
@@ -434,13 +2329,181 @@ mod benchmark_test_2 {
             query_throughput
         );
 
+        let start_scan = Instant::now();
+        let scanned: usize = (0..NODE_COUNT)
+            .into_par_iter()
+            .map(|i| linear_scan(&cg, i, QUERY_TIMESTAMP).len())
+            .sum();
+        let duration_scan = start_scan.elapsed();
+        println!(
+            "[CG Dense] Linear scan: {:?}, sorted lookup: {:?} ({:.1}x faster)",
+            duration_scan,
+            duration_query,
+            speedup(duration_scan, duration_query)
+        );
+        assert_eq!(total_neighbors, 11 * NODE_COUNT);
+        assert_eq!(scanned, total_neighbors);
+
         println!("[CG Dense] Memory: {} bytes", size_of_val(&cg));
     }
+
+    /// The dense scenario with longer per-node histories, where a query
+    /// early in each history skips most of its edges.
+    const HISTORY_NODE_COUNT: usize = 500;
+    const HISTORY_EDGE_PER_NODE: usize = 1_000;
+
+    #[test]
+    fn benchmark_tg_dense_history() {
+        let mut tg = TemporalGraph::new();
+        (0..HISTORY_NODE_COUNT).for_each(|i| tg.add_node(i));
+        (0..HISTORY_NODE_COUNT).for_each(|i| {
+            (0..HISTORY_EDGE_PER_NODE).for_each(|j| {
+                let _ = tg.add_edge(i, (i + j + 1) % HISTORY_NODE_COUNT, (j as u64) * 5);
+            });
+        });
+
+        let start_query = Instant::now();
+        let total_neighbors: usize = (0..HISTORY_NODE_COUNT)
+            .map(|i| tg.get_neighbors_at(i, QUERY_TIMESTAMP).len())
+            .sum();
+        let duration_query = start_query.elapsed();
+
+        let start_scan = Instant::now();
+        let scanned: usize = (0..HISTORY_NODE_COUNT).map(|i| linear_scan(&tg, i, QUERY_TIMESTAMP).len()).sum();
+        let duration_scan = start_scan.elapsed();
+        println!(
+            "[TG Dense History] Linear scan: {:?}, sorted lookup: {:?} ({:.1}x faster)",
+            duration_scan,
+            duration_query,
+            speedup(duration_scan, duration_query)
+        );
+        assert_eq!(total_neighbors, 11 * HISTORY_NODE_COUNT);
+        assert_eq!(scanned, total_neighbors);
+    }
+
+    #[test]
+    fn benchmark_cg_dense_history() {
+        let mut cg = ChronoGraph::new();
+        (0..HISTORY_NODE_COUNT).for_each(|i| cg.add_node(i));
+        (0..HISTORY_NODE_COUNT).for_each(|i| {
+            (0..HISTORY_EDGE_PER_NODE).for_each(|j| {
+                let _ = cg.add_edge(i, (i + j + 1) % HISTORY_NODE_COUNT, (j as u64) * 5);
+            });
+        });
+
+        let start_query = Instant::now();
+        let total_neighbors: usize = (0..HISTORY_NODE_COUNT)
+            .into_par_iter()
+            .map(|i| cg.get_neighbors_at(i, QUERY_TIMESTAMP).len())
+            .sum();
+        let duration_query = start_query.elapsed();
+
+        let start_scan = Instant::now();
+        let scanned: usize = (0..HISTORY_NODE_COUNT)
+            .into_par_iter()
+            .map(|i| linear_scan(&cg, i, QUERY_TIMESTAMP).len())
+            .sum();
+        let duration_scan = start_scan.elapsed();
+        println!(
+            "[CG Dense History] Linear scan: {:?}, sorted lookup: {:?} ({:.1}x faster)",
+            duration_scan,
+            duration_query,
+            speedup(duration_scan, duration_query)
+        );
+        assert_eq!(total_neighbors, 11 * HISTORY_NODE_COUNT);
+        assert_eq!(scanned, total_neighbors);
+    }
+
+    #[test]
+    fn benchmark_tg_hub() {
+        let mut tg = TemporalGraph::new();
+        const HUB_DEGREE: usize = 20_000;
+
+        (0..NODE_COUNT).for_each(|i| tg.add_node(i));
+        (0..HUB_DEGREE).for_each(|j| {
+            // Interleave late and early timestamps to exercise out-of-order inserts.
+            let ts = if j % 2 == 0 { j as u64 } else { (HUB_DEGREE - j) as u64 };
+            let _ = tg.add_edge(0, 1 + j % (NODE_COUNT - 1), ts);
+        });
+
+        let start_query = Instant::now();
+        let total_neighbors: usize = (0..NODE_COUNT as u64)
+            .map(|t| tg.get_neighbors_at(0, t).len())
+            .sum();
+        let duration_query = start_query.elapsed();
+
+        let query_throughput = NODE_COUNT as f64 / duration_query.as_secs_f64();
+        println!("[TG Hub] Total neighbors: {}, throughput: {:.2} queries/sec", total_neighbors, query_throughput);
+
+        let start_scan = Instant::now();
+        let scanned: usize = (0..NODE_COUNT as u64).map(|t| linear_scan(&tg, 0, t).len()).sum();
+        let duration_scan = start_scan.elapsed();
+        println!(
+            "[TG Hub] Linear scan: {:?}, sorted lookup: {:?} ({:.1}x faster)",
+            duration_scan,
+            duration_query,
+            speedup(duration_scan, duration_query)
+        );
+        assert_eq!(scanned, total_neighbors);
+        for t in [0, 1, 777, 9_999, HUB_DEGREE as u64] {
+            assert_eq!(tg.get_neighbors_at(0, t), linear_scan(&tg, 0, t));
+        }
+        assert_eq!(tg.get_neighbors_at(0, 0), vec![1]);
+        assert_eq!(tg.get_neighbors_at(0, HUB_DEGREE as u64).len(), HUB_DEGREE);
+    }
+
+    #[test]
+    fn benchmark_cg_hub() {
+        let mut cg = ChronoGraph::new();
+        const HUB_DEGREE: usize = 20_000;
+
+        (0..NODE_COUNT).for_each(|i| cg.add_node(i));
+        (0..HUB_DEGREE).for_each(|j| {
+            // Interleave late and early timestamps to exercise out-of-order inserts.
+            let ts = if j % 2 == 0 { j as u64 } else { (HUB_DEGREE - j) as u64 };
+            let _ = cg.add_edge(0, 1 + j % (NODE_COUNT - 1), ts);
+        });
+
+        let start_query = Instant::now();
+        let total_neighbors: usize = (0..NODE_COUNT as u64)
+            .into_par_iter()
+            .map(|t| cg.get_neighbors_at(0, t).len())
+            .sum();
+        let duration_query = start_query.elapsed();
+
+        let query_throughput = NODE_COUNT as f64 / duration_query.as_secs_f64();
+        println!(
+            "[CG Hub] Total neighbors: {}, throughput: {:.2} queries/sec",
+            total_neighbors,
+            query_throughput
+        );
+
+        let start_scan = Instant::now();
+        let scanned: usize = (0..NODE_COUNT as u64)
+            .into_par_iter()
+            .map(|t| linear_scan(&cg, 0, t).len())
+            .sum();
+        let duration_scan = start_scan.elapsed();
+        println!(
+            "[CG Hub] Linear scan: {:?}, sorted lookup: {:?} ({:.1}x faster)",
+            duration_scan,
+            duration_query,
+            speedup(duration_scan, duration_query)
+        );
+        assert_eq!(scanned, total_neighbors);
+        for t in [0, 1, 777, 9_999, HUB_DEGREE as u64] {
+            assert_eq!(cg.get_neighbors_at(0, t), linear_scan(&cg, 0, t));
+        }
+        assert_eq!(cg.get_neighbors_at(0, 0), vec![1]);
+        assert_eq!(cg.get_neighbors_at(0, HUB_DEGREE as u64).len(), HUB_DEGREE);
+    }
 }
 
+#[cfg(test)]
 mod benchmark_test_3 {
     use super::sequential::*;
     use super::chrono::*;
+    use super::ops::TemporalGraphOps;
     use rayon::prelude::*;
     use std::mem::size_of_val;
     use std::time::Instant;
@@ -456,7 +2519,6 @@ mod benchmark_test_3 {
 
         let mut tg = TemporalGraph::new();
         let mut rng = rand::thread_rng();
-        const EDGE_PER_NODE: usize = 10;
 
         (0..NODE_COUNT).for_each(|i| tg.add_node(i));
 
@@ -500,7 +2562,6 @@ mod benchmark_test_3 {
 
         let mut cg = ChronoGraph::new();
         let mut rng = rand::thread_rng();
-        const EDGE_PER_NODE: usize = 10;
 
         (0..NODE_COUNT).for_each(|i| cg.add_node(i));
 
@@ -539,9 +2600,10 @@ mod benchmark_test_3 {
     }
 }
 
+#[cfg(test)]
 mod scalability_test {
-    use super::sequential::*;
     use super::chrono::*;
+    use super::ops::TemporalGraphOps;
     use rayon::prelude::*;
     use rayon::ThreadPoolBuilder;
     use std::mem::size_of_val;
@@ -618,4 +2680,37 @@ mod scalability_test {
             );
         });
     }
+
+    #[test]
+    fn scalability_per_graph_pools() {
+        // Each graph owns its pool, so several thread counts can coexist.
+        for threads in [1, 2, 4, 8] {
+            let mut cg = ChronoGraph::new_with_threads(threads).expect("Failed to build graph thread pool");
+
+            (0..NODE_COUNT).for_each(|i| cg.add_node(i));
+            (0..NODE_COUNT).for_each(|i| {
+                (0..EDGE_PER_NODE).for_each(|j| {
+                    let _ = cg.add_edge(i, (i + j + 1) % NODE_COUNT, (j as u64) * 10);
+                });
+            });
+
+            let start_query = Instant::now();
+            let total_neighbors: usize = cg.install(|| {
+                (0..NODE_COUNT)
+                    .into_par_iter()
+                    .map(|i| cg.get_neighbors_at(i, QUERY_TIMESTAMP).len())
+                    .sum()
+            });
+            let duration_query = start_query.elapsed();
+
+            let query_throughput = NODE_COUNT as f64 / duration_query.as_secs_f64();
+
+            println!(
+                "[CG Scalability] {} threads: total neighbors: {}, throughput: {:.2} queries/sec",
+                threads,
+                total_neighbors,
+                query_throughput
+            );
+        }
+    }
 }
@@ -1,9 +1,10 @@
-/// src/main.rs
-///
-/// # Author
-/// Yifan Jiang, 2025
-/// alvinjiang01@hotmail.com
-/// GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//! src/main.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+use chronograph::ops::TemporalGraphOps;
 use chronograph::sequential::TemporalGraph;
 
 fn main() {
@@ -12,10 +13,11 @@ fn main() {
     tg.add_node(1);
     tg.add_node(2);
     tg.add_node(3);
-    tg.add_edge(1, 2, 5); // edge active at time 5
-    tg.add_edge(1, 3, 10);
-    tg.add_edge(2, 3, 15);
+    tg.add_edge(1, 2, 5).unwrap(); // edge active from time 5 onwards
+    tg.add_edge_interval(1, 3, 10, 20).unwrap(); // edge active during [10, 20)
+    tg.add_edge(2, 3, 15).unwrap();
     tg.print();
     println!("Neighbors of 1 at time 6: {:?}", tg.get_neighbors_at(1, 6));
     println!("Neighbors of 1 at time 12: {:?}", tg.get_neighbors_at(1, 12));
+    println!("Neighbors of 1 at time 25: {:?}", tg.get_neighbors_at(1, 25));
 }
\ No newline at end of file
@@ -0,0 +1,475 @@
+//! src/motifs.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module counts δ-temporal motifs (Paranjape, Benson and Leskovec,
+//! "Motifs in Temporal Networks", WSDM 2017): sequences of three edges
+//! on at most three nodes, occurring at strictly increasing times with
+//! the last no more than δ after the first.
+//!
+//! Every edge is one event at the start of its interval, in the
+//! direction it was added; self-loops are ignored. Each undirected edge
+//! counts once. Counting uses the full edge history, regardless of node
+//! lifetimes.
+//!
+//! Counting follows the paper's counter-based algorithms rather than
+//! enumerating instances. Pair and star motifs are counted in one sweep
+//! over the events at each node, in `O(m)` time overall for `m` events.
+//! Triangle motifs are counted with one sweep per triangle of the static
+//! graph over the events of its three node pairs, so an event is visited
+//! once for every triangle its pair belongs to.
+
+use std::collections::HashMap;
+use std::fmt;
+use crate::ops::TemporalGraphOps;
+use crate::types::TimeSpan;
+
+/// Number of distinct 3-edge motifs on at most three nodes.
+pub const MOTIF_COUNT: usize = 36;
+
+/// The shape of a motif's underlying static graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MotifKind {
+    /// All three edges join the same two nodes.
+    Pair,
+    /// Three nodes, with one pair of them never joined.
+    Star,
+    /// Three nodes, every pair joined by one edge.
+    Triangle,
+}
+
+/// A 3-edge temporal motif in canonical form.
+///
+/// Nodes are numbered `0`, `1`, `2` in order of first appearance, so the
+/// first edge is always `0 -> 1`. Edges are listed in time order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Motif {
+    edges: [(u8, u8); 3],
+}
+
+impl Motif {
+    /// Creates a motif from its edges, or returns `None` unless the first
+    /// edge is `0 -> 1` and every edge joins two distinct nodes below `3`.
+    pub fn new(edges: [(u8, u8); 3]) -> Option<Self> {
+        let valid = edges[0] == (0, 1) && edges.iter().all(|&(a, b)| a != b && a < 3 && b < 3);
+        valid.then_some(Self { edges })
+    }
+
+    /// Returns all 36 motifs in ascending order of their edge lists, the
+    /// order used by [`MotifCounts`].
+    pub fn all() -> Vec<Motif> {
+        (0..MOTIF_COUNT).map(Motif::from_index).collect()
+    }
+
+    pub fn edges(&self) -> [(u8, u8); 3] {
+        self.edges
+    }
+
+    pub fn node_count(&self) -> usize {
+        if self.edges.iter().any(|&(a, b)| a == 2 || b == 2) {
+            3
+        } else {
+            2
+        }
+    }
+
+    pub fn kind(&self) -> MotifKind {
+        if self.node_count() == 2 {
+            return MotifKind::Pair;
+        }
+        let joined = |x: u8, y: u8| self.edges.iter().any(|&(a, b)| (a, b) == (x, y) || (a, b) == (y, x));
+        if joined(0, 1) && joined(0, 2) && joined(1, 2) {
+            MotifKind::Triangle
+        } else {
+            MotifKind::Star
+        }
+    }
+
+    /// Returns the position of this motif in [`Motif::all`].
+    ///
+    /// Any second and third edge among three nodes yields a canonical
+    /// motif, so the position is the pair of their ranks in base 6.
+    pub fn index(&self) -> usize {
+        rank(self.edges[1]) * 6 + rank(self.edges[2])
+    }
+
+    fn from_index(index: usize) -> Self {
+        Self { edges: [(0, 1), unrank(index / 6), unrank(index % 6)] }
+    }
+}
+
+impl fmt::Display for Motif {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let edges: Vec<String> = self.edges.iter().map(|(a, b)| format!("{}->{}", a, b)).collect();
+        write!(f, "{}", edges.join(", "))
+    }
+}
+
+/// Instance counts for every motif, indexed as in [`Motif::all`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MotifCounts {
+    counts: [u64; MOTIF_COUNT],
+}
+
+impl MotifCounts {
+    pub(crate) fn from_array(counts: [u64; MOTIF_COUNT]) -> Self {
+        Self { counts }
+    }
+
+    pub fn get(&self, motif: &Motif) -> u64 {
+        self.counts[motif.index()]
+    }
+
+    /// Returns the counts in the order of [`Motif::all`].
+    pub fn as_array(&self) -> &[u64; MOTIF_COUNT] {
+        &self.counts
+    }
+
+    /// Returns every motif with its count, in the order of [`Motif::all`].
+    pub fn iter(&self) -> impl Iterator<Item = (Motif, u64)> + '_ {
+        Motif::all().into_iter().zip(self.counts.iter().copied())
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Returns the total count of the motifs of one kind.
+    pub fn total_of(&self, kind: MotifKind) -> u64 {
+        self.iter().filter(|(motif, _)| motif.kind() == kind).map(|(_, count)| count).sum()
+    }
+}
+
+/// Counts the instances of every 3-edge δ-temporal motif.
+pub fn count_motifs<G>(graph: &G, delta: G::Time) -> MotifCounts
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    let events = EventIndex::new(graph);
+    let mut counts = [0; MOTIF_COUNT];
+    for node in 0..events.node_count() {
+        events.count_from(node, delta, &mut counts);
+    }
+    MotifCounts::from_array(counts)
+}
+
+/// Returns the position of a directed edge among the six joining three
+/// nodes, in ascending order.
+fn rank((a, b): (u8, u8)) -> usize {
+    usize::from(a) * 2 + usize::from(if b > a { b - 1 } else { b })
+}
+
+fn unrank(rank: usize) -> (u8, u8) {
+    let a = (rank / 2) as u8;
+    let b = (rank % 2) as u8;
+    (a, if b >= a { b + 1 } else { b })
+}
+
+/// The edges of a graph as time-sorted events over dense node indices,
+/// shared by the sequential and parallel motif counters.
+pub(crate) struct EventIndex<T> {
+    // `(time, src, dst)` sorted by time.
+    events: Vec<(T, usize, usize)>,
+    // Events touching each node, in time order.
+    incident: Vec<Vec<usize>>,
+    // Events between each pair of nodes, smaller node first, in time order.
+    pairs: HashMap<(usize, usize), Vec<usize>>,
+    // Nodes sharing an event with each node, ascending.
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl<T: TimeSpan> EventIndex<T> {
+    pub(crate) fn new<G: TemporalGraphOps<Time = T>>(graph: &G) -> Self {
+        let index: HashMap<&G::Node, usize> = graph.get_nodes().iter().enumerate().map(|(i, node)| (node, i)).collect();
+        let index = &index;
+        let mut events: Vec<(T, usize, usize)> = graph
+            .get_edges()
+            .iter()
+            .flat_map(|(src, edges)| {
+                let src = index[src];
+                edges
+                    .iter()
+                    .filter(|edge| !edge.is_mirror())
+                    .map(move |edge| (edge.interval.start, src, index[&edge.dst]))
+            })
+            .filter(|&(_, src, dst)| src != dst)
+            .collect();
+        events.sort_by_key(|&(time, _, _)| time);
+
+        let mut incident = vec![Vec::new(); index.len()];
+        let mut pairs: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (i, &(_, src, dst)) in events.iter().enumerate() {
+            incident[src].push(i);
+            incident[dst].push(i);
+            pairs.entry((src.min(dst), src.max(dst))).or_default().push(i);
+        }
+        let mut neighbors = vec![Vec::new(); index.len()];
+        for &(a, b) in pairs.keys() {
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+        neighbors.iter_mut().for_each(|around| around.sort_unstable());
+        Self { events, incident, pairs, neighbors }
+    }
+
+    pub(crate) fn node_count(&self) -> usize {
+        self.incident.len()
+    }
+
+    /// Adds to `counts` the instances counted at `node`: the star motifs
+    /// centered on it, the pair motifs with a larger node, and the triangle
+    /// motifs whose smallest node it is.
+    pub(crate) fn count_from(&self, node: usize, delta: T, counts: &mut [u64; MOTIF_COUNT]) {
+        let around: Vec<(T, usize, usize)> = self.incident[node]
+            .iter()
+            .map(|&e| match self.events[e] {
+                (time, src, dst) if src == node => (time, dst, OUT),
+                (time, src, _) => (time, src, IN),
+            })
+            .collect();
+        StarCounts::new(&around, node, delta).add_to(counts);
+
+        let neighbors = &self.neighbors[node];
+        let larger = neighbors.partition_point(|&v| v < node);
+        for (i, &v) in neighbors.iter().enumerate().skip(larger) {
+            for &w in &neighbors[i + 1..] {
+                if self.neighbors[v].binary_search(&w).is_ok() {
+                    self.count_triangle([node, v, w], delta, counts);
+                }
+            }
+        }
+    }
+
+    /// Adds the triangle motifs on three nodes to `counts`, counting the
+    /// event triples over their three pairs that use every pair once.
+    fn count_triangle(&self, nodes: [usize; 3], delta: T, counts: &mut [u64; MOTIF_COUNT]) {
+        let [u, v, w] = nodes;
+        let mut merged: Vec<usize> = [(u, v), (u, w), (v, w)].iter().flat_map(|pair| &self.pairs[pair]).copied().collect();
+        merged.sort_unstable();
+        let position = |node: usize| nodes.iter().position(|&x| x == node).unwrap() as u8;
+        let labelled: Vec<(T, usize)> = merged
+            .into_iter()
+            .map(|e| {
+                let (time, src, dst) = self.events[e];
+                (time, rank((position(src), position(dst))))
+            })
+            .collect();
+
+        let triples: [[[u64; 6]; 6]; 6] = count_triples(&labelled, delta);
+        let edge = |label: usize| {
+            let (a, b) = unrank(label);
+            (usize::from(a), usize::from(b))
+        };
+        // The node pair of an edge, identified by the sum of its nodes.
+        let pair = |label: usize| edge(label).0 + edge(label).1;
+        for l1 in 0..6 {
+            for l2 in 0..6 {
+                for l3 in 0..6 {
+                    if pair(l1) != pair(l2) && pair(l1) != pair(l3) && pair(l2) != pair(l3) {
+                        counts[canonical_index([edge(l1), edge(l2), edge(l3)])] += triples[l1][l2][l3];
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns whether an event at `time` is more than `delta` before `now`.
+fn expired<T: TimeSpan>(time: T, now: T, delta: T) -> bool {
+    time.checked_add(delta).is_some_and(|last| last < now)
+}
+
+/// Returns the end of the run of events sharing the time of the one at
+/// `begin`.
+fn run_end<E, T: PartialEq>(events: &[E], begin: usize, time: impl Fn(&E) -> T) -> usize {
+    let now = time(&events[begin]);
+    begin + events[begin..].iter().take_while(|&event| time(event) == now).count()
+}
+
+/// Counts the triples of time-sorted labelled events at strictly
+/// increasing times spanning at most `delta`, by their labels.
+///
+/// This is the paper's sliding-window counter: the window holds the
+/// events at most `delta` before the current one, with counts of its
+/// single events and ordered pairs by label. Events sharing a time are
+/// added and removed together so that none of them are ordered.
+fn count_triples<T: TimeSpan, const L: usize>(events: &[(T, usize)], delta: T) -> [[[u64; L]; L]; L] {
+    let mut ones = [0u64; L];
+    let mut pairs = [[0u64; L]; L];
+    let mut triples = [[[0u64; L]; L]; L];
+    let (mut first, mut begin) = (0, 0);
+    while begin < events.len() {
+        let now = events[begin].0;
+        let end = run_end(events, begin, |event| event.0);
+        while first < begin && expired(events[first].0, now, delta) {
+            let stale = run_end(events, first, |event| event.0);
+            events[first..stale].iter().for_each(|&(_, label)| ones[label] -= 1);
+            for &(_, label) in &events[first..stale] {
+                pairs[label].iter_mut().zip(ones).for_each(|(pair, one)| *pair -= one);
+            }
+            first = stale;
+        }
+        for &(_, label) in &events[begin..end] {
+            for (a, row) in pairs.iter().enumerate() {
+                for (b, &pair) in row.iter().enumerate() {
+                    triples[a][b][label] += pair;
+                }
+            }
+        }
+        for &(_, label) in &events[begin..end] {
+            for (a, &one) in ones.iter().enumerate() {
+                pairs[a][label] += one;
+            }
+        }
+        events[begin..end].iter().for_each(|&(_, label)| ones[label] += 1);
+        begin = end;
+    }
+    triples
+}
+
+/// Directions of an event at the center of a star.
+const OUT: usize = 0;
+const IN: usize = 1;
+
+/// Event triple counts by the direction of each event at the center.
+type Directions = [[[u64; 2]; 2]; 2];
+
+/// The event triples at one center node, split by which of the three
+/// events share their other node.
+#[derive(Default)]
+struct StarCounts {
+    // All three, when the other node is larger than the center.
+    pairs: Directions,
+    // The first two only.
+    first_two: Directions,
+    // The first and the last only.
+    outer: Directions,
+    // The last two only.
+    last_two: Directions,
+}
+
+/// The window events between the center and one of its neighbors.
+#[derive(Default, Clone, Copy)]
+struct Neighbor {
+    // Events by direction.
+    ones: [u64; 2],
+    // Ordered pairs of these events, by directions.
+    pairs: [[u64; 2]; 2],
+    // Over these events by direction, the sum of the center's events
+    // before their time, and up to and including it, by direction.
+    before: [[u64; 2]; 2],
+    through: [[u64; 2]; 2],
+}
+
+impl StarCounts {
+    /// Counts the triples of `events`, given as `(time, neighbor,
+    /// direction)` in time order, with one sweep of a sliding window.
+    ///
+    /// Besides the pair counts of [`count_triples`], the window keeps
+    /// running totals per neighbor of how many of the center's events
+    /// came before each of its events, which count the triples whose
+    /// shared neighbor is not the last event's.
+    fn new<T: TimeSpan>(events: &[(T, usize, usize)], center: usize, delta: T) -> Self {
+        let mut counts = Self::default();
+        let mut neighbors: HashMap<usize, Neighbor> = HashMap::new();
+        // Window pairs sharing a neighbor, by directions.
+        let mut shared = [[0u64; 2]; 2];
+        // Events before the current time, and those of them out of the window.
+        let (mut seen, mut dropped) = ([0u64; 2], [0u64; 2]);
+        // `seen` before each event's time, and after it.
+        let mut prefixes: Vec<([u64; 2], [u64; 2])> = Vec::with_capacity(events.len());
+        let (mut first, mut begin) = (0, 0);
+        while begin < events.len() {
+            let now = events[begin].0;
+            let end = run_end(events, begin, |event| event.0);
+            while first < begin && expired(events[first].0, now, delta) {
+                let stale = run_end(events, first, |event| event.0);
+                for (&(_, neighbor, dir), (before, through)) in events[first..stale].iter().zip(&prefixes[first..stale]) {
+                    let n = neighbors.get_mut(&neighbor).unwrap();
+                    n.ones[dir] -= 1;
+                    for d in [OUT, IN] {
+                        n.before[dir][d] -= before[d];
+                        n.through[dir][d] -= through[d];
+                    }
+                    dropped[dir] += 1;
+                }
+                for &(_, neighbor, dir) in &events[first..stale] {
+                    let n = neighbors.get_mut(&neighbor).unwrap();
+                    for d in [OUT, IN] {
+                        shared[dir][d] -= n.ones[d];
+                        n.pairs[dir][d] -= n.ones[d];
+                    }
+                }
+                first = stale;
+            }
+
+            for &(_, neighbor, d3) in &events[begin..end] {
+                let n = neighbors.get(&neighbor).copied().unwrap_or_default();
+                for (d1, d2) in [(OUT, OUT), (OUT, IN), (IN, OUT), (IN, IN)] {
+                    let same = n.pairs[d1][d2];
+                    counts.first_two[d1][d2][d3] += shared[d1][d2] - same;
+                    counts.outer[d1][d2][d3] += n.ones[d1] * seen[d2] - n.through[d1][d2] - same;
+                    counts.last_two[d1][d2][d3] += n.before[d2][d1] - n.ones[d2] * dropped[d1] - same;
+                    if neighbor > center {
+                        counts.pairs[d1][d2][d3] += same;
+                    }
+                }
+            }
+            for &(_, neighbor, dir) in &events[begin..end] {
+                let n = neighbors.entry(neighbor).or_default();
+                for d in [OUT, IN] {
+                    shared[d][dir] += n.ones[d];
+                    n.pairs[d][dir] += n.ones[d];
+                }
+            }
+            let before = seen;
+            events[begin..end].iter().for_each(|&(_, _, dir)| seen[dir] += 1);
+            for &(_, neighbor, dir) in &events[begin..end] {
+                let n = neighbors.get_mut(&neighbor).unwrap();
+                n.ones[dir] += 1;
+                for d in [OUT, IN] {
+                    n.before[dir][d] += before[d];
+                    n.through[dir][d] += seen[d];
+                }
+            }
+            prefixes.resize(end, (before, seen));
+            begin = end;
+        }
+        counts
+    }
+
+    /// Adds these triples to `counts` as motifs, with the center as node
+    /// `0` and the neighbors as `1` and `2`.
+    fn add_to(&self, counts: &mut [u64; MOTIF_COUNT]) {
+        let edge = |dir: usize, other: usize| if dir == OUT { (0, other) } else { (other, 0) };
+        let (x, y) = (|dir| edge(dir, 1), |dir| edge(dir, 2));
+        for d1 in [OUT, IN] {
+            for d2 in [OUT, IN] {
+                for d3 in [OUT, IN] {
+                    counts[canonical_index([x(d1), x(d2), x(d3)])] += self.pairs[d1][d2][d3];
+                    counts[canonical_index([x(d1), x(d2), y(d3)])] += self.first_two[d1][d2][d3];
+                    counts[canonical_index([x(d1), y(d2), x(d3)])] += self.outer[d1][d2][d3];
+                    counts[canonical_index([x(d1), y(d2), y(d3)])] += self.last_two[d1][d2][d3];
+                }
+            }
+        }
+    }
+}
+
+/// Relabels the nodes of three edges in order of first appearance and
+/// returns the position of the resulting motif in [`Motif::all`].
+fn canonical_index(edges: [(usize, usize); 3]) -> usize {
+    let mut seen: Vec<usize> = Vec::with_capacity(3);
+    let mut label = |node: usize| match seen.iter().position(|&x| x == node) {
+        Some(i) => i as u8,
+        None => {
+            seen.push(node);
+            (seen.len() - 1) as u8
+        }
+    };
+    Motif { edges: edges.map(|(a, b)| (label(a), label(b))) }.index()
+}
@@ -0,0 +1,278 @@
+//! src/ops.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module defines [`TemporalGraphOps`], the API shared by
+//! [`TemporalGraph`](crate::sequential::TemporalGraph) and
+//! [`ChronoGraph`](crate::chrono::ChronoGraph), so that algorithms,
+//! importers and tests can be written once for either backend.
+//!
+//! Backends only hand out their [`GraphStore`]; every other method is
+//! provided on top of it, and a backend overrides the ones it can run
+//! differently, as `ChronoGraph` does to run queries in parallel.
+//!
+//! Every time window `[t_start, t_end)` taken here or by the algorithm
+//! modules is half-open like [`Interval`]: it includes `t_start`,
+//! excludes `t_end`, and is empty unless `t_start < t_end`.
+
+use std::collections::HashSet;
+use crate::edge::{AdjacencyMap, Interval, Lifetime, Properties, TemporalEdge};
+use crate::error::GraphError;
+use crate::snapshot::StaticGraph;
+use crate::store::GraphStore;
+use crate::types::{Directionality, NodeKey, Time, TimeSpan};
+
+/// Read and mutation operations common to every temporal graph backend.
+pub trait TemporalGraphOps {
+    type Node: NodeKey;
+    type Time: Time;
+
+    /// Returns the storage the provided methods read.
+    fn store(&self) -> &GraphStore<Self::Node, Self::Time>;
+
+    /// Returns the storage the provided methods mutate.
+    fn store_mut(&mut self) -> &mut GraphStore<Self::Node, Self::Time>;
+
+    fn directionality(&self) -> Directionality {
+        self.store().directionality
+    }
+
+    fn is_directed(&self) -> bool {
+        self.directionality() == Directionality::Directed
+    }
+
+    fn get_nodes(&self) -> &HashSet<Self::Node> {
+        &self.store().nodes
+    }
+
+    fn get_edges(&self) -> &AdjacencyMap<Self::Node, Self::Time> {
+        &self.store().edges
+    }
+
+    /// Returns the outgoing edges of `node`, sorted by interval start.
+    fn edges_of(&self, node: &Self::Node) -> &[TemporalEdge<Self::Node, Self::Time>] {
+        self.get_edges().get(node).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    fn contains_node(&self, node: &Self::Node) -> bool {
+        self.get_nodes().contains(node)
+    }
+
+    /// Returns the number of edges, counting each undirected edge once.
+    fn edge_count(&self) -> usize {
+        self.store().edge_count()
+    }
+
+    /// Returns the number of edges leaving `node` (all incident edges if undirected).
+    fn out_degree(&self, node: &Self::Node) -> usize {
+        self.store().out_degree(node)
+    }
+
+    /// Returns the number of edges entering `node` (all incident edges if undirected).
+    fn in_degree(&self, node: &Self::Node) -> usize {
+        if !self.is_directed() {
+            return self.out_degree(node);
+        }
+        self.store().in_degree(node)
+    }
+
+    /// Returns the number of edges incident to `node`. Directed graphs add
+    /// the in- and out-degree; undirected graphs count each edge once.
+    fn degree(&self, node: &Self::Node) -> usize {
+        if self.is_directed() {
+            self.out_degree(node) + self.in_degree(node)
+        } else {
+            self.out_degree(node)
+        }
+    }
+
+    /// Builds the incoming-edge index from the current edges and keeps it
+    /// up to date on every later mutation. Does nothing if already enabled
+    /// or if the graph is undirected, since edges are then stored under
+    /// both endpoints anyway.
+    fn enable_in_index(&mut self) {
+        self.store_mut().enable_in_index();
+    }
+
+    fn has_in_index(&self) -> bool {
+        self.store().in_edges.is_some()
+    }
+
+    fn add_node(&mut self, node_id: Self::Node) {
+        self.store_mut().add_node(node_id);
+    }
+
+    /// Adds a node that comes into existence at `timestamp`.
+    ///
+    /// Re-adding an existing node resets its lifetime to `[timestamp, ∞)`.
+    fn add_node_at(&mut self, node_id: Self::Node, timestamp: Self::Time) {
+        self.store_mut().add_node_at(node_id, timestamp);
+    }
+
+    /// Ends the lifetime of `node_id` at `timestamp` while keeping the node
+    /// and its edges, so queries before `timestamp` still see it.
+    ///
+    /// Use [`remove_node`](Self::remove_node) to erase a node from history.
+    fn remove_node_at(&mut self, node_id: Self::Node, timestamp: Self::Time) -> Result<Self::Node, GraphError<Self::Node, Self::Time>> {
+        self.store_mut().remove_node_at(node_id, timestamp)
+    }
+
+    /// Returns `true` if `node` exists and is alive at `timestamp`.
+    ///
+    /// Nodes added with [`add_node`](Self::add_node) are alive at all times.
+    fn is_alive_at(&self, node: &Self::Node, timestamp: Self::Time) -> bool {
+        self.store().is_alive_at(node, timestamp)
+    }
+
+    /// Returns the existence interval of `node`, or `None` if it is unknown.
+    fn node_lifetime(&self, node: &Self::Node) -> Option<Lifetime<Self::Time>> {
+        self.store().node_lifetime(node)
+    }
+
+    /// Returns every node alive at `timestamp`.
+    fn nodes_at(&self, timestamp: Self::Time) -> Vec<Self::Node> {
+        self.store().nodes_at(timestamp)
+    }
+
+    /// Adds an edge that becomes active at `timestamp` and never expires.
+    fn add_edge(&mut self, src: Self::Node, dst: Self::Node, timestamp: Self::Time) -> Result<(), GraphError<Self::Node, Self::Time>> {
+        self.store_mut().add_edge(src, dst, Interval::starting_at(timestamp))
+    }
+
+    /// Adds an edge that is active during `[start, end)`.
+    fn add_edge_interval(&mut self, src: Self::Node, dst: Self::Node, start: Self::Time, end: Self::Time) -> Result<(), GraphError<Self::Node, Self::Time>> {
+        self.store_mut().add_edge(src, dst, Interval::new(start, end))
+    }
+
+    /// Adds an edge active during `interval` carrying a weight and
+    /// typed properties.
+    fn add_edge_with(
+        &mut self,
+        src: Self::Node,
+        dst: Self::Node,
+        interval: Interval<Self::Time>,
+        weight: f64,
+        properties: Properties,
+    ) -> Result<(), GraphError<Self::Node, Self::Time>> {
+        self.store_mut().add_edge_with(src, dst, interval, weight, properties)
+    }
+
+    /// Adds an edge active during `interval` that takes `duration` to
+    /// traverse, as used by the path algorithms in [`paths`](crate::paths).
+    fn add_edge_with_duration(
+        &mut self,
+        src: Self::Node,
+        dst: Self::Node,
+        interval: Interval<Self::Time>,
+        duration: Self::Time,
+    ) -> Result<(), GraphError<Self::Node, Self::Time>>
+    where
+        Self::Time: TimeSpan,
+    {
+        self.store_mut().add_edge_with_duration(src, dst, interval, duration)
+    }
+
+    /// Removes `node_id` and every edge touching it from all of history.
+    ///
+    /// Undirected graphs and graphs with the incoming-edge index enabled
+    /// only visit the node's own neighbors; otherwise every adjacency list
+    /// is scanned.
+    fn remove_node(&mut self, node_id: Self::Node) -> Result<Self::Node, GraphError<Self::Node, Self::Time>> {
+        self.store_mut().remove_node(node_id)
+    }
+
+    /// Removes the edge from `src` to `dst` whose interval starts at `timestamp`.
+    /// In an undirected graph the endpoints may be given in either order.
+    #[allow(clippy::type_complexity)]
+    fn remove_edge(&mut self, src: Self::Node, dst: Self::Node, timestamp: Self::Time) -> Result<(Self::Node, Self::Node, Self::Time), GraphError<Self::Node, Self::Time>> {
+        self.store_mut().remove_edge(src, dst, timestamp)
+    }
+
+    /// Returns the destinations of `node`'s edges that are active at `timestamp`.
+    fn get_neighbors_at(&self, node: Self::Node, timestamp: Self::Time) -> Vec<Self::Node> {
+        self.store().get_neighbors_at(&node, timestamp)
+    }
+
+    /// Returns `node`'s outgoing edges that are active at `timestamp`,
+    /// including their weights and properties.
+    fn get_edges_at(&self, node: Self::Node, timestamp: Self::Time) -> Vec<&TemporalEdge<Self::Node, Self::Time>> {
+        self.store().get_edges_at(&node, timestamp)
+    }
+
+    /// Returns the destinations of `node`'s edges that are active at
+    /// `timestamp` and satisfy `filter`, e.g. a weight or property check.
+    fn get_neighbors_at_filtered<F>(&self, node: Self::Node, timestamp: Self::Time, filter: F) -> Vec<Self::Node>
+    where
+        F: Fn(&TemporalEdge<Self::Node, Self::Time>) -> bool + Sync,
+    {
+        self.store().get_neighbors_at_filtered(&node, timestamp, filter)
+    }
+
+    /// Returns the distinct destinations of `node`'s edges that are active
+    /// at some point within `[t_start, t_end)`, in order of first contact.
+    fn get_neighbors_between(&self, node: Self::Node, t_start: Self::Time, t_end: Self::Time) -> Vec<Self::Node> {
+        self.store().get_neighbors_between(&node, t_start, t_end)
+    }
+
+    /// Returns every edge active at some point within `[t_start, t_end)`
+    /// between nodes alive at some point in it, as `(source, edge)` pairs,
+    /// listing each undirected edge once.
+    #[allow(clippy::type_complexity)]
+    fn edges_in_range(&self, t_start: Self::Time, t_end: Self::Time) -> Vec<(Self::Node, &TemporalEdge<Self::Node, Self::Time>)> {
+        self.store().edges_in_range(t_start, t_end)
+    }
+
+    /// Returns when the earliest edge from `src` to `dst` first joins
+    /// them while both are alive.
+    fn first_contact(&self, src: &Self::Node, dst: &Self::Node) -> Option<Self::Time> {
+        self.store().first_contact(src, dst)
+    }
+
+    /// Returns when the most recent edge from `src` to `dst` first joins
+    /// them while both are alive.
+    fn last_contact(&self, src: &Self::Node, dst: &Self::Node) -> Option<Self::Time> {
+        self.store().last_contact(src, dst)
+    }
+
+    /// Materializes the graph as it is at `timestamp`: the nodes alive
+    /// then and the edges active between them.
+    fn snapshot_at(&self, timestamp: Self::Time) -> StaticGraph<Self::Node> {
+        self.store().snapshot_at(timestamp)
+    }
+
+    /// Materializes the graph over `[t_start, t_end)`: the nodes alive at
+    /// some point in the window and the edges active at some point in it.
+    fn snapshot_between(&self, t_start: Self::Time, t_end: Self::Time) -> StaticGraph<Self::Node> {
+        self.store().snapshot_between(t_start, t_end)
+    }
+
+    /// Returns the sources of edges into `node` that are active at `timestamp`.
+    ///
+    /// Uses the incoming-edge index when enabled and falls back to
+    /// scanning every adjacency list otherwise. In an undirected graph this
+    /// is the same as [`get_neighbors_at`](Self::get_neighbors_at).
+    fn get_in_neighbors_at(&self, node: Self::Node, timestamp: Self::Time) -> Vec<Self::Node> {
+        if !self.is_directed() {
+            return self.get_neighbors_at(node, timestamp);
+        }
+        self.store().get_in_neighbors_at(&node, timestamp)
+    }
+
+    /// Returns the nodes connected to `node` in either direction at
+    /// `timestamp`, each listed once.
+    fn get_all_neighbors_at(&self, node: Self::Node, timestamp: Self::Time) -> Vec<Self::Node> {
+        let mut seen = HashSet::new();
+        self.get_neighbors_at(node.clone(), timestamp)
+            .into_iter()
+            .chain(self.get_in_neighbors_at(node, timestamp))
+            .filter(|neighbor| seen.insert(neighbor.clone()))
+            .collect()
+    }
+
+    fn print(&self) {
+        self.store().print();
+    }
+}
@@ -0,0 +1,124 @@
+//! src/pagerank.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module implements temporal PageRank (Rozenshtein and Gionis,
+//! "Temporal PageRank", ECML PKDD 2016), which scores nodes by the
+//! time-respecting random walks that end at them. Edges are streamed in
+//! order of their start time, each one a single event in the direction
+//! it was added; an undirected edge is one event each way.
+
+use std::collections::HashMap;
+use crate::edge::started_by;
+use crate::ops::TemporalGraphOps;
+use crate::types::{NodeId, NodeKey, Time};
+
+/// Parameters of temporal PageRank.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageRankConfig {
+    /// Probability that a walk follows the next edge out of its node
+    /// rather than stopping; `1 - alpha` is the teleport probability.
+    pub alpha: f64,
+    /// Share of the walks at a node that stay there after one of them
+    /// leaves along an edge. Smaller values favour recent edges; `1.0`
+    /// disables the decay.
+    pub beta: f64,
+}
+
+impl Default for PageRankConfig {
+    fn default() -> Self {
+        Self { alpha: 0.85, beta: 0.5 }
+    }
+}
+
+/// Streaming temporal PageRank: feed edges in time order with
+/// [`process_edge`](Self::process_edge) and read the scores at any point.
+#[derive(Debug, Clone)]
+pub struct TemporalPageRank<N = NodeId> {
+    config: PageRankConfig,
+    // Unnormalized score: total mass of walks that ended at each node.
+    rank: HashMap<N, f64>,
+    // Mass of walks currently waiting at each node.
+    walkers: HashMap<N, f64>,
+}
+
+impl<N: NodeKey> TemporalPageRank<N> {
+    pub fn new(config: PageRankConfig) -> Self {
+        Self {
+            config,
+            rank: HashMap::new(),
+            walkers: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> PageRankConfig {
+        self.config
+    }
+
+    /// Processes one edge `src -> dst`, which must not start before any
+    /// edge already processed.
+    pub fn process_edge(&mut self, src: &N, dst: &N) {
+        let PageRankConfig { alpha, beta } = self.config;
+        *self.rank.entry(src.clone()).or_default() += 1.0 - alpha;
+        let at_src = self.walkers.entry(src.clone()).or_default();
+        *at_src += 1.0 - alpha;
+        let moved = *at_src * alpha;
+        *at_src = if beta < 1.0 { *at_src * beta } else { 0.0 };
+        let carried = if beta < 1.0 { moved * (1.0 - beta) } else { moved };
+        *self.rank.entry(dst.clone()).or_default() += moved;
+        *self.walkers.entry(dst.clone()).or_default() += carried;
+    }
+
+    /// Returns the scores of every node seen so far, summing to `1`.
+    pub fn scores(&self) -> HashMap<N, f64> {
+        let total: f64 = self.rank.values().sum();
+        self.rank
+            .iter()
+            .map(|(node, &rank)| (node.clone(), if total > 0.0 { rank / total } else { 0.0 }))
+            .collect()
+    }
+
+    /// Returns the unnormalized scores of every node seen so far.
+    pub fn raw_scores(&self) -> &HashMap<N, f64> {
+        &self.rank
+    }
+}
+
+/// Computes temporal PageRank over the edges that started by `timestamp`.
+///
+/// Every node of the graph gets a score, `0.0` for nodes no walk has
+/// reached; the scores sum to `1` unless no edge has started. Edges
+/// sharing a start time are processed in order of the text of their
+/// source, then of their destination, so every backend gives the same
+/// scores.
+pub fn temporal_pagerank<G: TemporalGraphOps>(graph: &G, timestamp: G::Time, config: PageRankConfig) -> HashMap<G::Node, f64> {
+    let mut events: Vec<(G::Time, &G::Node, &G::Node)> = graph
+        .get_edges()
+        .iter()
+        .flat_map(|(src, edges)| started_by(edges, timestamp).iter().map(move |edge| (edge.interval.start, src, &edge.dst)))
+        .collect();
+    events.sort_by_cached_key(event_order);
+
+    let mut pagerank = TemporalPageRank::new(config);
+    for (_, src, dst) in events {
+        pagerank.process_edge(src, dst);
+    }
+    with_all_nodes(graph, pagerank.scores())
+}
+
+/// The key edge events are processed in: start time, then the text of
+/// the source and of the destination.
+pub(crate) fn event_order<N: NodeKey, T: Time>(&(start, src, dst): &(T, &N, &N)) -> (T, String, String) {
+    (start, src.to_string(), dst.to_string())
+}
+
+/// Adds a zero score for every node of `graph` missing from `scores`.
+pub(crate) fn with_all_nodes<G: TemporalGraphOps>(graph: &G, mut scores: HashMap<G::Node, f64>) -> HashMap<G::Node, f64> {
+    for node in graph.get_nodes() {
+        scores.entry(node.clone()).or_insert(0.0);
+    }
+    scores
+}
@@ -0,0 +1,606 @@
+//! src/paths.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module implements time-respecting path queries over any
+//! [`TemporalGraphOps`] backend. A path is time-respecting when every
+//! hop departs no earlier than the previous hop arrived.
+//!
+//! An edge active during `[start, end)` can be taken at any moment
+//! inside its interval and arrives after its
+//! [`duration`](TemporalEdge::duration), or instantly if it has none.
+//! Both endpoints must be alive when it is taken and the destination
+//! must still be alive on arrival. Travellers may wait at any node.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use crate::edge::{reverse_adjacency, AdjacencyMap, Lifetime, TemporalEdge};
+use crate::ops::TemporalGraphOps;
+use crate::types::{NodeId, NodeKey, TimeSpan, Timestamp};
+
+/// One traversal of an edge along a temporal path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hop<N = NodeId, T = Timestamp> {
+    pub src: N,
+    pub dst: N,
+    pub departure: T,
+    pub arrival: T,
+}
+
+/// Earliest arrival times from a single source, with the hops needed to
+/// reconstruct one earliest path to every reached node.
+#[derive(Debug, Clone)]
+pub struct EarliestArrival<N = NodeId, T = Timestamp> {
+    source: N,
+    start_time: T,
+    arrivals: HashMap<N, T>,
+    // The last hop of an earliest path to each reached node except the source.
+    last_hops: HashMap<N, Hop<N, T>>,
+}
+
+impl<N: NodeKey, T: TimeSpan> EarliestArrival<N, T> {
+    pub fn source(&self) -> &N {
+        &self.source
+    }
+
+    pub fn start_time(&self) -> T {
+        self.start_time
+    }
+
+    /// Returns the earliest time `node` can be reached, or `None` if it cannot.
+    pub fn arrival_time(&self, node: &N) -> Option<T> {
+        self.arrivals.get(node).copied()
+    }
+
+    pub fn is_reachable(&self, node: &N) -> bool {
+        self.arrivals.contains_key(node)
+    }
+
+    /// Returns the earliest arrival time of every reached node, including the source.
+    pub fn arrivals(&self) -> &HashMap<N, T> {
+        &self.arrivals
+    }
+
+    /// Returns the hops of an earliest path from the source to `node`,
+    /// empty for the source itself, or `None` if `node` is unreachable.
+    pub fn path_to(&self, node: &N) -> Option<Vec<Hop<N, T>>> {
+        if !self.arrivals.contains_key(node) {
+            return None;
+        }
+        let mut hops = Vec::new();
+        let mut current = node;
+        while let Some(hop) = self.last_hops.get(current) {
+            hops.push(hop.clone());
+            current = &hop.src;
+        }
+        hops.reverse();
+        Some(hops)
+    }
+}
+
+/// Latest departure times towards a single target, with the hops needed
+/// to reconstruct one latest-departure path from every node that can
+/// make the deadline.
+#[derive(Debug, Clone)]
+pub struct LatestDeparture<N = NodeId, T = Timestamp> {
+    target: N,
+    deadline: T,
+    departures: HashMap<N, T>,
+    // The first hop of a latest-departure path from each node except the target.
+    next_hops: HashMap<N, Hop<N, T>>,
+}
+
+impl<N: NodeKey, T: TimeSpan> LatestDeparture<N, T> {
+    pub fn target(&self) -> &N {
+        &self.target
+    }
+
+    pub fn deadline(&self) -> T {
+        self.deadline
+    }
+
+    /// Returns the latest time one can be at `node` and still reach the
+    /// target by the deadline, or `None` if that is impossible.
+    pub fn departure_time(&self, node: &N) -> Option<T> {
+        self.departures.get(node).copied()
+    }
+
+    pub fn can_reach(&self, node: &N) -> bool {
+        self.departures.contains_key(node)
+    }
+
+    /// Returns the latest departure time of every node that can make the
+    /// deadline, including the target itself.
+    pub fn departures(&self) -> &HashMap<N, T> {
+        &self.departures
+    }
+
+    /// Returns the hops of a latest-departure path from `node` to the
+    /// target, empty for the target itself, or `None` if `node` cannot
+    /// make the deadline.
+    pub fn path_from(&self, node: &N) -> Option<Vec<Hop<N, T>>> {
+        if !self.departures.contains_key(node) {
+            return None;
+        }
+        let mut hops = Vec::new();
+        let mut current = node;
+        while let Some(hop) = self.next_hops.get(current) {
+            hops.push(hop.clone());
+            current = &hop.dst;
+        }
+        Some(hops)
+    }
+}
+
+/// Minimum journey durations from a single source, measured from the
+/// departure at the source to the arrival at each reached node.
+#[derive(Debug, Clone)]
+pub struct FastestPaths<N = NodeId, T = Timestamp> {
+    source: N,
+    start_time: T,
+    durations: HashMap<N, T>,
+    paths: HashMap<N, Vec<Hop<N, T>>>,
+}
+
+impl<N: NodeKey, T: TimeSpan> FastestPaths<N, T> {
+    pub fn source(&self) -> &N {
+        &self.source
+    }
+
+    pub fn start_time(&self) -> T {
+        self.start_time
+    }
+
+    /// Returns the shortest journey duration to `node`, or `None` if it is unreachable.
+    pub fn duration(&self, node: &N) -> Option<T> {
+        self.durations.get(node).copied()
+    }
+
+    pub fn is_reachable(&self, node: &N) -> bool {
+        self.durations.contains_key(node)
+    }
+
+    /// Returns the shortest journey duration of every reached node,
+    /// including the source at zero.
+    pub fn durations(&self) -> &HashMap<N, T> {
+        &self.durations
+    }
+
+    /// Returns the hops of a fastest path from the source to `node`,
+    /// empty for the source itself, or `None` if `node` is unreachable.
+    pub fn path_to(&self, node: &N) -> Option<Vec<Hop<N, T>>> {
+        self.paths.get(node).cloned()
+    }
+}
+
+/// Fewest-hop journeys from a single source. Among the journeys with the
+/// fewest hops to a node, the one arriving earliest is kept.
+#[derive(Debug, Clone)]
+pub struct FewestHops<N = NodeId, T = Timestamp> {
+    source: N,
+    start_time: T,
+    hop_counts: HashMap<N, usize>,
+    arrivals: HashMap<N, T>,
+    // `levels[k]` holds the last hop of the earliest journey with at most
+    // `k` hops to every node that journey improved on.
+    levels: Vec<HashMap<N, Hop<N, T>>>,
+}
+
+impl<N: NodeKey, T: TimeSpan> FewestHops<N, T> {
+    pub fn source(&self) -> &N {
+        &self.source
+    }
+
+    pub fn start_time(&self) -> T {
+        self.start_time
+    }
+
+    /// Returns the fewest hops needed to reach `node`, or `None` if it is unreachable.
+    pub fn hop_count(&self, node: &N) -> Option<usize> {
+        self.hop_counts.get(node).copied()
+    }
+
+    /// Returns the earliest arrival at `node` among its fewest-hop journeys.
+    pub fn arrival_time(&self, node: &N) -> Option<T> {
+        self.arrivals.get(node).copied()
+    }
+
+    pub fn is_reachable(&self, node: &N) -> bool {
+        self.hop_counts.contains_key(node)
+    }
+
+    /// Returns the fewest hops needed to reach every reached node, including the source at zero.
+    pub fn hop_counts(&self) -> &HashMap<N, usize> {
+        &self.hop_counts
+    }
+
+    /// Returns the hops of a fewest-hop path from the source to `node`,
+    /// empty for the source itself, or `None` if `node` is unreachable.
+    pub fn path_to(&self, node: &N) -> Option<Vec<Hop<N, T>>> {
+        let mut level = self.hop_count(node)?;
+        let mut hops = Vec::with_capacity(level);
+        let mut current = node;
+        while level > 0 {
+            let hop = &self.levels[level][current];
+            hops.push(hop.clone());
+            current = &hop.src;
+            level -= 1;
+        }
+        hops.reverse();
+        Some(hops)
+    }
+}
+
+/// Computes the earliest time every node can be reached from `source`
+/// when leaving at `start_time`. A missing `source` reaches nothing.
+pub fn earliest_arrival<G>(graph: &G, source: G::Node, start_time: G::Time) -> EarliestArrival<G::Node, G::Time>
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    let tree = earliest_tree(graph, &source, start_time, None);
+    let mut arrivals = HashMap::with_capacity(tree.len());
+    let mut last_hops = HashMap::with_capacity(tree.len());
+    for (node, label) in tree {
+        arrivals.insert(node.clone(), label.arrival);
+        if let Some(via) = label.via {
+            last_hops.insert(node, via.hop);
+        }
+    }
+    EarliestArrival {
+        source,
+        start_time,
+        arrivals,
+        last_hops,
+    }
+}
+
+/// Computes, for every node, the latest time one can be there and still
+/// reach `target` by `deadline`. A missing `target` is reached by nothing.
+pub fn latest_departure<G>(graph: &G, target: G::Node, deadline: G::Time) -> LatestDeparture<G::Node, G::Time>
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    latest_departure_in(graph, &reverse_adjacency(graph.get_edges()), target, deadline)
+}
+
+/// [`latest_departure`] over a prebuilt reverse adjacency of `graph`, so
+/// several targets can share one.
+pub(crate) fn latest_departure_in<G>(
+    graph: &G,
+    reversed: &AdjacencyMap<G::Node, G::Time>,
+    target: G::Node,
+    deadline: G::Time,
+) -> LatestDeparture<G::Node, G::Time>
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    let mut result = LatestDeparture {
+        target: target.clone(),
+        deadline,
+        departures: HashMap::new(),
+        next_hops: HashMap::new(),
+    };
+    if !graph.contains_node(&target) {
+        return result;
+    }
+
+    // As in `earliest_tree`, the heap refers to nodes by slot in `queued`.
+    let mut queued = vec![target.clone()];
+    let mut heap = BinaryHeap::from([(deadline, 0)]);
+    result.departures.insert(target, deadline);
+
+    while let Some((ready_by, slot)) = heap.pop() {
+        let node = queued[slot].clone();
+        if result.departures.get(&node) != Some(&ready_by) {
+            continue;
+        }
+        let node_lifetime = graph.node_lifetime(&node).unwrap_or_default();
+        for incoming in reversed.get(&node).map(Vec::as_slice).unwrap_or(&[]) {
+            let src = &incoming.dst;
+            let src_lifetime = graph.node_lifetime(src).unwrap_or_default();
+            let Some((departure, arrival)) = latest_traversal(incoming, ready_by, &src_lifetime, &node_lifetime) else {
+                continue;
+            };
+            if result.departures.get(src).is_some_and(|&best| best >= departure) {
+                continue;
+            }
+            result.departures.insert(src.clone(), departure);
+            result.next_hops.insert(
+                src.clone(),
+                Hop {
+                    src: src.clone(),
+                    dst: node.clone(),
+                    departure,
+                    arrival,
+                },
+            );
+            heap.push((departure, queued.len()));
+            queued.push(src.clone());
+        }
+    }
+    result
+}
+
+/// Computes the minimum journey duration from `source` to every node,
+/// over journeys leaving `source` at or after `start_time`.
+///
+/// Some fastest journey to every node either leaves at `start_time` or
+/// leaves as late as possible to reach some node `u` by the time one of
+/// `u`'s edges opens. Every such departure is found with a latest-departure
+/// search towards `u`, then an earliest-arrival search runs from each one
+/// and each earliest path found is slid as late as its edges allow. That
+/// is one search per distinct edge opening time at each reachable node.
+pub fn fastest_paths<G>(graph: &G, source: G::Node, start_time: G::Time) -> FastestPaths<G::Node, G::Time>
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    let mut result = FastestPaths {
+        source: source.clone(),
+        start_time,
+        durations: HashMap::new(),
+        paths: HashMap::new(),
+    };
+    if !graph.contains_node(&source) {
+        return result;
+    }
+    result.durations.insert(source.clone(), G::Time::ZERO);
+    result.paths.insert(source.clone(), Vec::new());
+
+    let reversed = reverse_adjacency(graph.get_edges());
+    let mut departures = vec![start_time];
+    for (node, label) in earliest_tree(graph, &source, start_time, None) {
+        let lifetime = graph.node_lifetime(&node).unwrap_or_default();
+        let mut openings: Vec<G::Time> = graph
+            .edges_of(&node)
+            .iter()
+            .map(|edge| opening(edge, &lifetime, &graph.node_lifetime(&edge.dst).unwrap_or_default()))
+            .filter(|&opening| opening >= label.arrival)
+            .collect();
+        openings.sort();
+        openings.dedup();
+        for opening in openings {
+            let latest = latest_departure_in(graph, &reversed, node.clone(), opening);
+            departures.extend(latest.departure_time(&source).filter(|&departure| departure >= start_time));
+        }
+    }
+    departures.sort();
+    departures.dedup();
+
+    for departure in departures {
+        let tree = earliest_tree(graph, &source, departure, None);
+        for node in tree.keys() {
+            if *node == source {
+                continue;
+            }
+            let hops = slide_late(graph, &tree, node);
+            let duration = hops[hops.len() - 1]
+                .arrival
+                .checked_sub(hops[0].departure)
+                .expect("arrivals never precede departures");
+            if result.durations.get(node).is_none_or(|&best| duration < best) {
+                result.durations.insert(node.clone(), duration);
+                result.paths.insert(node.clone(), hops);
+            }
+        }
+    }
+    result
+}
+
+/// Computes the fewest hops needed to reach every node from `source`
+/// when leaving at `start_time`. A missing `source` reaches nothing.
+pub fn fewest_hops<G>(graph: &G, source: G::Node, start_time: G::Time) -> FewestHops<G::Node, G::Time>
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    let mut result = FewestHops {
+        source: source.clone(),
+        start_time,
+        hop_counts: HashMap::new(),
+        arrivals: HashMap::new(),
+        levels: vec![HashMap::new()],
+    };
+    if !graph.contains_node(&source) {
+        return result;
+    }
+    result.hop_counts.insert(source.clone(), 0);
+    result.arrivals.insert(source.clone(), start_time);
+
+    // Earliest arrival using at most the hops explored so far. A level
+    // only relaxes the nodes the previous level improved.
+    let mut best = HashMap::from([(source.clone(), start_time)]);
+    let mut frontier = vec![(source, start_time)];
+    while !frontier.is_empty() {
+        let mut level: HashMap<G::Node, Hop<G::Node, G::Time>> = HashMap::new();
+        for (node, ready) in &frontier {
+            let node_lifetime = graph.node_lifetime(node).unwrap_or_default();
+            for edge in graph.edges_of(node) {
+                let dst_lifetime = graph.node_lifetime(&edge.dst).unwrap_or_default();
+                let Some((departure, arrival)) = earliest_traversal(edge, *ready, &node_lifetime, &dst_lifetime) else {
+                    continue;
+                };
+                if best.get(&edge.dst).is_some_and(|&known| known <= arrival)
+                    || level.get(&edge.dst).is_some_and(|hop| hop.arrival <= arrival)
+                {
+                    continue;
+                }
+                level.insert(
+                    edge.dst.clone(),
+                    Hop {
+                        src: node.clone(),
+                        dst: edge.dst.clone(),
+                        departure,
+                        arrival,
+                    },
+                );
+            }
+        }
+
+        let depth = result.levels.len();
+        frontier = level.values().map(|hop| (hop.dst.clone(), hop.arrival)).collect();
+        for (node, arrival) in &frontier {
+            best.insert(node.clone(), *arrival);
+            if !result.hop_counts.contains_key(node) {
+                result.hop_counts.insert(node.clone(), depth);
+                result.arrivals.insert(node.clone(), *arrival);
+            }
+        }
+        result.levels.push(level);
+    }
+    result
+}
+
+/// A settled node of an earliest-arrival search, reached `via` a hop
+/// unless it is the source.
+struct Label<'a, N, T> {
+    arrival: T,
+    via: Option<Via<'a, N, T>>,
+}
+
+/// The hop that reached a node and the edge it traversed.
+struct Via<'a, N, T> {
+    hop: Hop<N, T>,
+    edge: &'a TemporalEdge<N, T>,
+}
+
+/// Returns the earliest arrival at every node reachable from `source`
+/// when leaving at `start_time` and arriving before `end`.
+pub(crate) fn arrivals_before<G>(graph: &G, source: &G::Node, start_time: G::Time, end: G::Time) -> HashMap<G::Node, G::Time>
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    earliest_tree(graph, source, start_time, Some(end))
+        .into_iter()
+        .map(|(node, label)| (node, label.arrival))
+        .collect()
+}
+
+/// Runs the earliest-arrival search from `source`, a Dijkstra search on
+/// arrival times, keeping the edge behind every hop. Arrivals at or
+/// after `end`, if given, are discarded.
+fn earliest_tree<'a, G>(
+    graph: &'a G,
+    source: &G::Node,
+    start_time: G::Time,
+    end: Option<G::Time>,
+) -> HashMap<G::Node, Label<'a, G::Node, G::Time>>
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    let mut labels = HashMap::new();
+    if !graph.contains_node(source) {
+        return labels;
+    }
+
+    // The heap holds `(arrival, slot)` where `slot` indexes `queued`,
+    // since node keys are not required to be ordered.
+    let mut queued = vec![source.clone()];
+    let mut heap = BinaryHeap::from([Reverse((start_time, 0))]);
+    labels.insert(source.clone(), Label { arrival: start_time, via: None });
+
+    while let Some(Reverse((ready, slot))) = heap.pop() {
+        let node = queued[slot].clone();
+        if labels.get(&node).map(|label| label.arrival) != Some(ready) {
+            continue;
+        }
+        let node_lifetime = graph.node_lifetime(&node).unwrap_or_default();
+        for edge in graph.edges_of(&node) {
+            let dst_lifetime = graph.node_lifetime(&edge.dst).unwrap_or_default();
+            let Some((departure, arrival)) = earliest_traversal(edge, ready, &node_lifetime, &dst_lifetime) else {
+                continue;
+            };
+            if end.is_some_and(|end| arrival >= end)
+                || labels.get(&edge.dst).is_some_and(|label| label.arrival <= arrival)
+            {
+                continue;
+            }
+            let hop = Hop {
+                src: node.clone(),
+                dst: edge.dst.clone(),
+                departure,
+                arrival,
+            };
+            labels.insert(edge.dst.clone(), Label { arrival, via: Some(Via { hop, edge }) });
+            heap.push(Reverse((arrival, queued.len())));
+            queued.push(edge.dst.clone());
+        }
+    }
+    labels
+}
+
+/// Rebuilds the earliest path to `node` from `tree`, then moves every
+/// hop as late as possible without arriving later at `node`.
+fn slide_late<G>(graph: &G, tree: &HashMap<G::Node, Label<'_, G::Node, G::Time>>, node: &G::Node) -> Vec<Hop<G::Node, G::Time>>
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    let mut hops = Vec::new();
+    let mut ready_by = tree[node].arrival;
+    let mut current = node;
+    while let Some(Via { hop, edge }) = &tree[current].via {
+        let src_lifetime = graph.node_lifetime(&hop.src).unwrap_or_default();
+        let dst_lifetime = graph.node_lifetime(&hop.dst).unwrap_or_default();
+        // The hop as found is feasible, so a latest traversal always exists.
+        let (departure, arrival) = latest_traversal(edge, ready_by, &src_lifetime, &dst_lifetime).unwrap_or((hop.departure, hop.arrival));
+        hops.push(Hop {
+            src: hop.src.clone(),
+            dst: hop.dst.clone(),
+            departure,
+            arrival,
+        });
+        ready_by = departure;
+        current = &hop.src;
+    }
+    hops.reverse();
+    hops
+}
+
+fn duration_of<N, T: TimeSpan>(edge: &TemporalEdge<N, T>) -> T {
+    edge.duration.unwrap_or(T::ZERO)
+}
+
+/// Returns the earliest time `edge` can be taken, once it has started
+/// and both its endpoints exist.
+fn opening<N, T: TimeSpan>(edge: &TemporalEdge<N, T>, src: &Lifetime<T>, dst: &Lifetime<T>) -> T {
+    [src.born, dst.born].into_iter().flatten().fold(edge.interval.start, T::max)
+}
+
+/// Returns the earliest `(departure, arrival)` for taking `edge` at or
+/// after `ready`, or `None` if it can no longer be taken.
+pub(crate) fn earliest_traversal<N, T: TimeSpan>(edge: &TemporalEdge<N, T>, ready: T, src: &Lifetime<T>, dst: &Lifetime<T>) -> Option<(T, T)> {
+    let departure = [Some(ready), Some(edge.interval.start), src.born, dst.born]
+        .into_iter()
+        .flatten()
+        .max()?;
+    let arrival = departure.checked_add(duration_of(edge))?;
+    let feasible = edge.interval.contains(departure) && src.contains(departure) && dst.contains(departure) && dst.contains(arrival);
+    feasible.then_some((departure, arrival))
+}
+
+/// Returns the latest `(departure, arrival)` for taking `edge` so that it
+/// arrives by `ready_by`, or `None` if it cannot.
+pub(crate) fn latest_traversal<N, T: TimeSpan>(edge: &TemporalEdge<N, T>, ready_by: T, src: &Lifetime<T>, dst: &Lifetime<T>) -> Option<(T, T)> {
+    let duration = duration_of(edge);
+    let mut departure = ready_by.checked_sub(duration)?;
+    if let Some(end) = edge.interval.end {
+        departure = departure.min(end.checked_sub(T::STEP)?);
+    }
+    if let Some(died) = src.died {
+        departure = departure.min(died.checked_sub(T::STEP)?);
+    }
+    if let Some(died) = dst.died {
+        departure = departure.min(died.checked_sub(T::STEP)?.checked_sub(duration)?);
+    }
+    let earliest = [Some(edge.interval.start), src.born, dst.born].into_iter().flatten().max()?;
+    (departure >= earliest).then(|| (departure, departure.checked_add(duration).expect("departure + duration <= ready_by")))
+}
@@ -0,0 +1,464 @@
+//! src/persist.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module implements the binary graph file format behind
+//! `save_to` and `load_from` on both graph backends.
+//!
+//! A file is laid out as:
+//!
+//! ```text
+//! magic "CHRG" | major: u8 | minor: u8 | section* | end tag 0 | crc32: u32 LE
+//! section = tag: u8 | length: varint | payload
+//! ```
+//!
+//! The checksum covers every byte before it. Integers are LEB128
+//! varints, zigzag-encoded when signed; floats are 8 bytes little-endian.
+//!
+//! The nodes section lists every node once, in the byte order of their
+//! encodings, and edges refer to their endpoints by position in it, so
+//! the same graph always saves to the same bytes. Loading rejects edges
+//! with empty intervals or negative durations and nodes that die before
+//! they are born.
+//!
+//! Readers reject files with a different major version. Within a major
+//! version, newer writers may add sections or append fields to the end
+//! of a section's payload, and older readers skip both.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
+use crate::edge::{Interval, Lifetime, Properties, PropertyValue, TemporalEdge};
+use crate::error::PersistError;
+use crate::ops::TemporalGraphOps;
+use crate::types::{Directionality, NodeKey, Time};
+
+/// File signature at the start of every graph file.
+pub const MAGIC: [u8; 4] = *b"CHRG";
+/// Major format version; files with another major version are rejected.
+pub const FORMAT_MAJOR: u8 = 1;
+/// Minor format version, raised for backward-compatible additions.
+pub const FORMAT_MINOR: u8 = 0;
+
+const END: u8 = 0;
+const HEADER: u8 = 1;
+const NODES: u8 = 2;
+const EDGES: u8 = 3;
+
+/// Graphs that can be saved to and loaded from the graph file format,
+/// as needed by [`DurableGraph`](crate::wal::DurableGraph) checkpoints.
+pub trait Persistent: TemporalGraphOps + Sized {
+    /// Writes the graph to `writer`.
+    fn save_to<W: Write>(&self, writer: W) -> Result<(), PersistError>;
+
+    /// Reads a graph written by [`save_to`](Self::save_to).
+    fn load_from<R: Read>(reader: R) -> Result<Self, PersistError>;
+}
+
+/// Values that can be written to and read back from a graph file.
+///
+/// Implemented for the primitive integer types and `String`, which
+/// covers the node keys and timestamps used in practice.
+pub trait Codec: Sized {
+    fn encode(&self, out: &mut Vec<u8>);
+
+    /// Reads a value from the front of `input`, advancing past it.
+    fn decode(input: &mut &[u8]) -> Result<Self, PersistError>;
+
+    /// Returns `true` for values below zero, which no edge duration may be.
+    fn is_negative(&self) -> bool {
+        false
+    }
+}
+
+macro_rules! impl_unsigned_codec {
+    ($($t:ty),*) => {
+        $(
+            impl Codec for $t {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    write_varint(out, *self as u128);
+                }
+
+                fn decode(input: &mut &[u8]) -> Result<Self, PersistError> {
+                    <$t>::try_from(read_varint(input)?).map_err(|_| corrupt("integer out of range"))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_signed_codec {
+    ($($t:ty),*) => {
+        $(
+            impl Codec for $t {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    let value = *self as i128;
+                    write_varint(out, ((value << 1) ^ (value >> 127)) as u128);
+                }
+
+                fn decode(input: &mut &[u8]) -> Result<Self, PersistError> {
+                    let zigzag = read_varint(input)?;
+                    let value = (zigzag >> 1) as i128 ^ -((zigzag & 1) as i128);
+                    <$t>::try_from(value).map_err(|_| corrupt("integer out of range"))
+                }
+
+                fn is_negative(&self) -> bool {
+                    *self < 0
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned_codec!(u8, u16, u32, u64, u128, usize);
+impl_signed_codec!(i8, i16, i32, i64, i128, isize);
+
+impl Codec for String {
+    fn encode(&self, out: &mut Vec<u8>) {
+        write_varint(out, self.len() as u128);
+        out.extend_from_slice(self.as_bytes());
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, PersistError> {
+        let len = read_len(input)?;
+        let bytes = take(input, len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| corrupt("string is not valid UTF-8"))
+    }
+}
+
+/// A node and its lifetime, `None` for nodes that exist at all times.
+type NodeEntry<N, T> = (N, Option<Lifetime<T>>);
+
+/// An edge and its source node.
+type EdgeEntry<N, T> = (N, TemporalEdge<N, T>);
+
+/// A decoded graph file, ready to be loaded into either backend.
+pub(crate) struct GraphImage<N, T> {
+    pub(crate) directionality: Directionality,
+    pub(crate) in_index: bool,
+    pub(crate) nodes: Vec<NodeEntry<N, T>>,
+    pub(crate) edges: Vec<EdgeEntry<N, T>>,
+}
+
+/// Writes `graph` to `writer` in the graph file format.
+///
+/// Each undirected edge is written once, under the endpoint it was added from.
+pub(crate) fn write_graph<G, W>(graph: &G, mut writer: W) -> Result<(), PersistError>
+where
+    G: TemporalGraphOps,
+    G::Node: Codec,
+    G::Time: Codec,
+    W: Write,
+{
+    let mut out = Vec::new();
+    out.extend_from_slice(&MAGIC);
+    out.extend_from_slice(&[FORMAT_MAJOR, FORMAT_MINOR]);
+
+    let directed = graph.directionality() == Directionality::Directed;
+    write_section(&mut out, HEADER, &[u8::from(!directed), u8::from(graph.has_in_index())]);
+
+    let mut nodes: Vec<(Vec<u8>, &G::Node)> = graph
+        .get_nodes()
+        .iter()
+        .map(|node| {
+            let mut key = Vec::new();
+            node.encode(&mut key);
+            (key, node)
+        })
+        .collect();
+    nodes.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+    let index: HashMap<&G::Node, usize> = nodes.iter().enumerate().map(|(i, &(_, node))| (node, i)).collect();
+
+    let mut payload = Vec::new();
+    write_varint(&mut payload, nodes.len() as u128);
+    for (key, node) in &nodes {
+        payload.extend_from_slice(key);
+        let lifetime = graph.node_lifetime(node).unwrap_or_default();
+        payload.push(u8::from(lifetime.born.is_some()) | u8::from(lifetime.died.is_some()) << 1);
+        lifetime.born.iter().chain(&lifetime.died).for_each(|t| t.encode(&mut payload));
+    }
+    write_section(&mut out, NODES, &payload);
+
+    payload.clear();
+    let edges: Vec<_> = nodes
+        .iter()
+        .enumerate()
+        .flat_map(|(src, &(_, node))| graph.edges_of(node).iter().filter(|edge| !edge.is_mirror()).map(move |edge| (src, edge)))
+        .collect();
+    write_varint(&mut payload, edges.len() as u128);
+    for (src, edge) in edges {
+        write_varint(&mut payload, src as u128);
+        write_varint(&mut payload, index[&edge.dst] as u128);
+        write_edge_fields(&mut payload, edge);
+    }
+    write_section(&mut out, EDGES, &payload);
+
+    out.push(END);
+    let checksum = crc32(&out);
+    out.extend_from_slice(&checksum.to_le_bytes());
+    writer.write_all(&out)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads a graph file from `reader`, checking its signature, version and
+/// checksum before decoding it.
+pub(crate) fn read_graph<N, T, R>(mut reader: R) -> Result<GraphImage<N, T>, PersistError>
+where
+    N: NodeKey + Codec,
+    T: Time + Codec,
+    R: Read,
+{
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    if bytes.len() < MAGIC.len() || bytes[..MAGIC.len()] != MAGIC {
+        return Err(PersistError::BadMagic);
+    }
+    let Some(&[major, minor]) = bytes.get(MAGIC.len()..MAGIC.len() + 2) else {
+        return Err(corrupt("truncated header"));
+    };
+    if major != FORMAT_MAJOR {
+        return Err(PersistError::UnsupportedVersion { major, minor });
+    }
+    let Some(body_len) = bytes.len().checked_sub(4).filter(|&len| len >= MAGIC.len() + 2) else {
+        return Err(corrupt("missing checksum"));
+    };
+    let (body, trailer) = bytes.split_at(body_len);
+    let stored = u32::from_le_bytes(trailer.try_into().expect("trailer is four bytes"));
+    let computed = crc32(body);
+    if stored != computed {
+        return Err(PersistError::ChecksumMismatch { stored, computed });
+    }
+
+    let mut input = &body[MAGIC.len() + 2..];
+    let mut header = None;
+    let mut nodes = None;
+    let mut edges = None;
+    loop {
+        let tag = take(&mut input, 1)?[0];
+        if tag == END {
+            break;
+        }
+        let len = read_len(&mut input)?;
+        let mut payload = take(&mut input, len)?;
+        match tag {
+            HEADER => header = Some(take(&mut payload, 2)?.to_vec()),
+            NODES => nodes = Some(read_nodes(&mut payload)?),
+            // Decoded once the node table is known.
+            EDGES => edges = Some(payload),
+            // Sections added by newer minor versions.
+            _ => {}
+        }
+    }
+    if !input.is_empty() {
+        return Err(corrupt("data after the end marker"));
+    }
+
+    let header = header.ok_or_else(|| corrupt("missing header section"))?;
+    let directionality = match header[0] {
+        0 => Directionality::Directed,
+        1 => Directionality::Undirected,
+        other => return Err(corrupt(format!("unknown directionality {}", other))),
+    };
+    let nodes = nodes.ok_or_else(|| corrupt("missing nodes section"))?;
+    let edges = read_edges(&mut edges.ok_or_else(|| corrupt("missing edges section"))?, &nodes)?;
+    Ok(GraphImage {
+        directionality,
+        in_index: header[1] != 0,
+        nodes,
+        edges,
+    })
+}
+
+fn read_nodes<N: NodeKey + Codec, T: Time + Codec>(input: &mut &[u8]) -> Result<Vec<NodeEntry<N, T>>, PersistError> {
+    let count = read_len(input)?;
+    let mut seen = HashSet::new();
+    let mut nodes = Vec::new();
+    for _ in 0..count {
+        let node = N::decode(input)?;
+        if !seen.insert(node.clone()) {
+            return Err(corrupt(format!("node {} appears twice", node)));
+        }
+        let flags = take(input, 1)?[0];
+        let born = if flags & 1 != 0 { Some(T::decode(input)?) } else { None };
+        let died = if flags & 2 != 0 { Some(T::decode(input)?) } else { None };
+        if let (Some(born), Some(died)) = (born, died) {
+            if died <= born {
+                return Err(corrupt(format!("node {} dies at {} before it is born at {}", node, died, born)));
+            }
+        }
+        let lifetime = (born.is_some() || died.is_some()).then_some(Lifetime { born, died });
+        nodes.push((node, lifetime));
+    }
+    Ok(nodes)
+}
+
+/// Writes `edge` with its source `src`, naming both endpoints in full.
+pub(crate) fn write_edge<N: Codec, T: Codec>(out: &mut Vec<u8>, src: &N, edge: &TemporalEdge<N, T>) {
+    src.encode(out);
+    edge.dst.encode(out);
+    write_edge_fields(out, edge);
+}
+
+/// Writes everything about `edge` but its endpoints.
+///
+/// Flags: bit 0 is set when the interval has an end, bit 1 when the edge has a duration.
+fn write_edge_fields<N, T: Codec>(out: &mut Vec<u8>, edge: &TemporalEdge<N, T>) {
+    out.push(u8::from(edge.interval.end.is_some()) | u8::from(edge.duration.is_some()) << 1);
+    edge.interval.start.encode(out);
+    edge.interval.end.iter().chain(&edge.duration).for_each(|t| t.encode(out));
+    out.extend_from_slice(&edge.weight.to_le_bytes());
+    write_varint(out, edge.properties.len() as u128);
+    for (key, value) in &edge.properties {
+        key.encode(out);
+        match value {
+            PropertyValue::Str(value) => {
+                out.push(0);
+                value.encode(out);
+            }
+            PropertyValue::Int(value) => {
+                out.push(1);
+                value.encode(out);
+            }
+            PropertyValue::Float(value) => {
+                out.push(2);
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+            PropertyValue::Bool(value) => out.extend_from_slice(&[3, u8::from(*value)]),
+        }
+    }
+}
+
+/// Reads the edges section, whose endpoints are positions in `nodes`.
+fn read_edges<N: NodeKey, T: Time + Codec>(input: &mut &[u8], nodes: &[NodeEntry<N, T>]) -> Result<Vec<EdgeEntry<N, T>>, PersistError> {
+    let node = |input: &mut &[u8]| {
+        let position = usize::decode(input)?;
+        nodes
+            .get(position)
+            .map(|(node, _)| node.clone())
+            .ok_or_else(|| corrupt(format!("edge endpoint {} is past the {} nodes", position, nodes.len())))
+    };
+    let count = read_len(input)?;
+    (0..count)
+        .map(|_| {
+            let src = node(input)?;
+            let dst = node(input)?;
+            Ok((src, read_edge_fields(input, dst)?))
+        })
+        .collect()
+}
+
+/// Reads an edge written by [`write_edge`].
+pub(crate) fn read_edge<N: NodeKey + Codec, T: Time + Codec>(input: &mut &[u8]) -> Result<EdgeEntry<N, T>, PersistError> {
+    let src = N::decode(input)?;
+    let dst = N::decode(input)?;
+    Ok((src, read_edge_fields(input, dst)?))
+}
+
+/// Reads the fields written by [`write_edge_fields`] into an edge to `dst`.
+fn read_edge_fields<N: NodeKey, T: Time + Codec>(input: &mut &[u8], dst: N) -> Result<TemporalEdge<N, T>, PersistError> {
+    let flags = take(input, 1)?[0];
+    let start = T::decode(input)?;
+    let end = if flags & 1 != 0 { Some(T::decode(input)?) } else { None };
+    if let Some(end) = end.filter(|&end| end <= start) {
+        return Err(corrupt(format!("edge interval [{}, {}) is empty", start, end)));
+    }
+    let duration = if flags & 2 != 0 { Some(T::decode(input)?) } else { None };
+    if let Some(duration) = duration.filter(Codec::is_negative) {
+        return Err(corrupt(format!("edge duration {} is negative", duration)));
+    }
+    let weight = read_f64(input)?;
+
+    let mut properties = Properties::new();
+    for _ in 0..read_len(input)? {
+        let key = String::decode(input)?;
+        let value = match take(input, 1)?[0] {
+            0 => PropertyValue::Str(String::decode(input)?),
+            1 => PropertyValue::Int(i64::decode(input)?),
+            2 => PropertyValue::Float(read_f64(input)?),
+            3 => PropertyValue::Bool(take(input, 1)?[0] != 0),
+            other => return Err(corrupt(format!("unknown property type {}", other))),
+        };
+        properties.insert(key, value);
+    }
+
+    let mut edge = TemporalEdge::new(dst, Interval { start, end }).with_weight(weight);
+    edge.properties = properties;
+    edge.duration = duration;
+    Ok(edge)
+}
+
+fn write_section(out: &mut Vec<u8>, tag: u8, payload: &[u8]) {
+    out.push(tag);
+    write_varint(out, payload.len() as u128);
+    out.extend_from_slice(payload);
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u128) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(input: &mut &[u8]) -> Result<u128, PersistError> {
+    let mut value = 0u128;
+    for shift in (0..128).step_by(7) {
+        let byte = take(input, 1)?[0];
+        value |= u128::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(corrupt("varint is too long"))
+}
+
+/// Reads a length or count, bounded by the bytes left so that corrupt
+/// input cannot trigger huge allocations.
+fn read_len(input: &mut &[u8]) -> Result<usize, PersistError> {
+    let len = read_varint(input)?;
+    usize::try_from(len)
+        .ok()
+        .filter(|&len| len <= input.len())
+        .ok_or_else(|| corrupt("length exceeds the remaining data"))
+}
+
+fn read_f64(input: &mut &[u8]) -> Result<f64, PersistError> {
+    Ok(f64::from_le_bytes(take(input, 8)?.try_into().expect("took eight bytes")))
+}
+
+pub(crate) fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], PersistError> {
+    if input.len() < len {
+        return Err(corrupt("unexpected end of data"));
+    }
+    let (head, rest) = input.split_at(len);
+    *input = rest;
+    Ok(head)
+}
+
+pub(crate) fn corrupt(reason: impl Into<String>) -> PersistError {
+    PersistError::Corrupt(reason.into())
+}
+
+/// CRC-32 (IEEE 802.3, reflected polynomial `0xEDB88320`).
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut crc = i as u32;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+                bit += 1;
+            }
+            table[i] = crc;
+            i += 1;
+        }
+        table
+    };
+    !bytes.iter().fold(!0u32, |crc, &byte| TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8))
+}
@@ -0,0 +1,152 @@
+//! src/reachability.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module implements temporal reachability: which nodes a source
+//! can influence through time-respecting paths inside a time window,
+//! and [`ReachabilityMatrix`], the full relation stored as one bitset
+//! per source.
+
+use std::collections::{HashMap, HashSet};
+use crate::ops::TemporalGraphOps;
+use crate::paths;
+use crate::snapshot::index_nodes;
+use crate::types::{NodeId, NodeKey, TimeSpan};
+
+/// The temporal reachability relation over a fixed set of nodes.
+///
+/// Row `i` is a bitset over node indices with bit `j` set when the node
+/// at index `i` reaches the node at index `j`. A node is not counted as
+/// reaching itself.
+#[derive(Debug, Clone)]
+pub struct ReachabilityMatrix<N = NodeId> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    words_per_row: usize,
+    bits: Vec<u64>,
+}
+
+impl<N: NodeKey> ReachabilityMatrix<N> {
+    /// Assembles the matrix from one bitset row per entry of `nodes`.
+    pub(crate) fn from_rows(nodes: Vec<N>, index: HashMap<N, usize>, rows: Vec<Vec<u64>>) -> Self {
+        Self {
+            words_per_row: words_for(nodes.len()),
+            nodes,
+            index,
+            bits: rows.concat(),
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the node keys in index order.
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// Returns the index of `node`, or `None` if it is not in the matrix.
+    pub fn index_of(&self, node: &N) -> Option<usize> {
+        self.index.get(node).copied()
+    }
+
+    /// Returns the bitset of nodes reached from the node at `index`.
+    pub fn row(&self, index: usize) -> &[u64] {
+        &self.bits[index * self.words_per_row..(index + 1) * self.words_per_row]
+    }
+
+    /// Returns `true` if the node at index `i` reaches the node at index `j`.
+    pub fn reaches(&self, i: usize, j: usize) -> bool {
+        self.row(i)[j / 64] & (1 << (j % 64)) != 0
+    }
+
+    /// Returns the indices of the nodes reached from the node at `index`, in order.
+    pub fn reached_indices(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.row(index).iter().enumerate().flat_map(|(w, &word)| {
+            let mut bits = word;
+            std::iter::from_fn(move || {
+                (bits != 0).then(|| {
+                    let bit = bits.trailing_zeros() as usize;
+                    bits &= bits - 1;
+                    w * 64 + bit
+                })
+            })
+        })
+    }
+
+    /// Returns `true` if `src` reaches `dst`.
+    pub fn can_reach(&self, src: &N, dst: &N) -> bool {
+        match (self.index_of(src), self.index_of(dst)) {
+            (Some(i), Some(j)) => self.reaches(i, j),
+            _ => false,
+        }
+    }
+
+    /// Returns how many nodes `src` reaches.
+    pub fn reachable_count(&self, src: &N) -> usize {
+        self.index_of(src)
+            .map(|i| self.row(i).iter().map(|word| word.count_ones() as usize).sum())
+            .unwrap_or(0)
+    }
+
+    /// Returns the nodes `src` reaches, in index order.
+    pub fn reachable_from(&self, src: &N) -> Vec<&N> {
+        match self.index_of(src) {
+            Some(i) => self.reached_indices(i).map(|j| &self.nodes[j]).collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Returns the nodes `node` can reach through time-respecting paths that
+/// leave it no earlier than `t_start` and arrive before `t_end`.
+/// The source itself is not included.
+pub fn reachable_from<G>(graph: &G, node: G::Node, t_start: G::Time, t_end: G::Time) -> HashSet<G::Node>
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    if t_end <= t_start {
+        return HashSet::new();
+    }
+    let mut reached: HashSet<G::Node> = paths::arrivals_before(graph, &node, t_start, t_end).into_keys().collect();
+    reached.remove(&node);
+    reached
+}
+
+/// Computes the reachability relation within `[t_start, t_end)` from every node.
+pub fn reachability_matrix<G>(graph: &G, t_start: G::Time, t_end: G::Time) -> ReachabilityMatrix<G::Node>
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    let nodes: Vec<G::Node> = graph.get_nodes().iter().cloned().collect();
+    let index = index_nodes(&nodes);
+    let rows = nodes
+        .iter()
+        .map(|source| reachability_row(graph, &index, source, t_start, t_end))
+        .collect();
+    ReachabilityMatrix::from_rows(nodes, index, rows)
+}
+
+/// Builds the bitset row of `source` over the nodes in `index`.
+pub(crate) fn reachability_row<G>(graph: &G, index: &HashMap<G::Node, usize>, source: &G::Node, t_start: G::Time, t_end: G::Time) -> Vec<u64>
+where
+    G: TemporalGraphOps,
+    G::Time: TimeSpan,
+{
+    let mut row = vec![0; words_for(index.len())];
+    for node in reachable_from(graph, source.clone(), t_start, t_end) {
+        let j = index[&node];
+        row[j / 64] |= 1 << (j % 64);
+    }
+    row
+}
+
+fn words_for(node_count: usize) -> usize {
+    node_count.div_ceil(64)
+}
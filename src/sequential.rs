@@ -1,117 +1,279 @@
-/// src/sequential.rs
-///
-/// # Author
-/// Yifan Jiang, 2025
-/// alvinjiang01@hotmail.com
-/// GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
-
-/// This module implements a simple temporal graph structure
-/// and provides basic functionalities to add nodes and edges.
+//! src/sequential.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module implements a simple temporal graph structure
+//! and provides basic functionalities to add nodes and edges.
 
 use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, Read, Write};
+use std::str::FromStr;
+use crate::error::PersistError;
+use crate::ops::TemporalGraphOps;
+use crate::paths::{self, EarliestArrival, FastestPaths, FewestHops, LatestDeparture};
+use crate::reachability::{self, ReachabilityMatrix};
+use crate::components;
+use crate::motifs::{self, MotifCounts};
+use crate::pagerank::{self, PageRankConfig};
+use crate::centrality;
+use crate::cores::{self, CoreEvolution};
+use crate::persist::{self, Codec, Persistent};
+use crate::import::{self, ImportError, ImportOptions, ImportReport, OnError};
+use crate::export;
+use crate::events::{self, EventReport};
+use crate::store::GraphStore;
+use crate::types::{Directionality, NodeId, NodeKey, Time, TimeSpan, Timestamp};
 
-type NodeId = usize; // Unique identifier for a node in the graph
-type Timestamp = u64; // TImestamp represented as a non-negative integer
-
-
-/// A sequential temporal graph data struture where edges are
-/// timestamped to indicate when they are active.
+/// A sequential temporal graph data struture where edges carry
+/// a `[start, end)` interval indicating when they are active.
+///
+/// Nodes must be added using [`add_node`](Self::add_node) before
+/// adding edges. Edges must be added using [`add_edge`](Self::add_edge)
+/// (open-ended) or [`add_edge_interval`](Self::add_edge_interval)
+/// (bounded) and with pre-existing source and destination nodes.
 ///
-/// Nodes must be added using [`add_node`] before adding edges.
-/// Edges must be added using [`add_edge`] and with pre-exisitng
-/// source and destination nodes.
+/// Time-sensitive neighbors are queried using
+/// [`get_neighbors_at`](Self::get_neighbors_at), which only returns
+/// edges whose interval contains the query time.
+/// Adjacency lists are kept sorted by interval start, so a query only
+/// inspects the edges that have already started.
 ///
-/// Time-sensitive neighbors are queried using [`get_neighbors_at`].
+/// The graph is generic over its node key `N` and timestamp `T`,
+/// which default to [`NodeId`] and [`Timestamp`].
 #[derive(Debug, Clone)]
-pub struct TemporalGraph {
-    edges: HashMap<NodeId, Vec<(NodeId, Timestamp)>>,
-    nodes: HashSet<NodeId>,
+pub struct TemporalGraph<N = NodeId, T = Timestamp> {
+    store: GraphStore<N, T>,
 }
 
-impl TemporalGraph {
+impl<N: NodeKey, T: Time> Default for TemporalGraph<N, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: NodeKey, T: Time> TemporalGraph<N, T> {
     pub fn new() -> Self {
-        Self {
-            edges: HashMap::new(),
-            nodes: HashSet::new(),
-        }
-    }
-
-    pub fn get_nodes(&self) -> &HashSet<NodeId> {
-        &self.nodes
-    }
-
-    pub fn get_edges(&self) -> &HashMap<NodeId, Vec<(NodeId, Timestamp)>> {
-        &self.edges
-    }
-
-    pub fn add_node(&mut self, node_id: NodeId) {
-        self.nodes.insert(node_id);
-    }
-
-    pub fn add_edge(&mut self, src: NodeId, dst: NodeId, timestamp: Timestamp) -> Result<(), String> {
-        if !self.nodes.contains(&src) || !self.nodes.contains(&dst) {
-            return Err("Source or destination node does not exist.".into());
-        }
-        self.edges
-            .entry(src)
-            .or_insert_with(Vec::new)
-            .push((dst, timestamp));
-        Ok(())
-    }
-
-    pub fn remove_node(&mut self, node_id: NodeId) -> Result<NodeId, bool> {
-        if !self.nodes.remove(&node_id) {
-            return Err(false);
-        }
-
-        self.edges.remove(&node_id);
-        
-        for neighbors in self.edges.values_mut() {
-            neighbors.retain(|(dst, _)| *dst != node_id);
-        }
-
-        Ok(node_id)
-    }
-
-    pub fn remove_edge(&mut self, src: NodeId, dst: NodeId, timestamp: Timestamp) -> Result<(NodeId, NodeId, Timestamp), bool> {
-        if let Some(neighbors) = self.edges.get_mut(&src) {
-            let before = neighbors.len();
-            neighbors.retain(|(n, ts)| !(*n == dst && *ts == timestamp));
-            if neighbors.len() < before {
-                return Ok((src, dst, timestamp));
-            }
-        }
-        Err(false)
-    }
-
-    pub fn get_neighbors_at(&self, node: NodeId, timestamp: Timestamp) -> Vec<NodeId> {
-        self.edges
-            .get(&node)
-            .map(|neighbors| {
-                neighbors
-                    .iter()
-                    .filter_map(|(neighbor, ts)| {
-                        if *ts <= timestamp {
-                            Some(*neighbor)
-                        } else {
-                            None
-                        }
-                    })
-                    .collect()
-            })
-            .unwrap_or_default()
-    }
-
-    pub fn print(&self) {
-        for node in &self.nodes {
-            println!("Node {}:", node);
-            if let Some(edges) = self.edges.get(node) {
-                for (dst, ts) in edges {
-                    println!("  -> {} @ {}", dst, ts);
-                }
-            } else {
-                println!("  (no outgoing edges)");
-            }
-        }
-    }
-}
\ No newline at end of file
+        Self::new_with_directionality(Directionality::Directed)
+    }
+
+    /// Creates an empty graph whose edges are symmetric.
+    pub fn new_undirected() -> Self {
+        Self::new_with_directionality(Directionality::Undirected)
+    }
+
+    pub fn new_with_directionality(directionality: Directionality) -> Self {
+        Self { store: GraphStore::new(directionality) }
+    }
+
+    /// Creates an empty graph that maintains an incoming-edge index.
+    pub fn new_with_in_index() -> Self {
+        let mut graph = Self::new();
+        graph.enable_in_index();
+        graph
+    }
+
+    /// Writes the graph to `writer` in the versioned, checksummed binary
+    /// format described in [`persist`], covering nodes, lifetimes, edges
+    /// and the incoming-edge index setting.
+    pub fn save_to<W: Write>(&self, writer: W) -> Result<(), PersistError>
+    where
+        N: Codec,
+        T: Codec,
+    {
+        persist::write_graph(self, writer)
+    }
+
+    /// Reads a graph written by [`save_to`](Self::save_to), from either backend.
+    pub fn load_from<R: Read>(reader: R) -> Result<Self, PersistError>
+    where
+        N: Codec,
+        T: Codec,
+    {
+        let store = GraphStore::from_image(persist::read_graph(reader)?)?;
+        Ok(Self { store })
+    }
+
+    /// Imports the edges of a text edge list, creating missing nodes; see
+    /// [`import::import_edges`].
+    pub fn import_edges<R: BufRead>(&mut self, reader: R, options: &ImportOptions) -> Result<ImportReport, ImportError>
+    where
+        N: FromStr,
+        T: FromStr,
+    {
+        import::import_edges(self, reader, options)
+    }
+
+    /// Writes the graph, or the part of it present in `window`, as
+    /// Graphviz DOT; see [`export::write_dot`].
+    pub fn write_dot<W: Write>(&self, writer: W, window: Option<(T, T)>) -> io::Result<()> {
+        export::write_dot(self, writer, window)
+    }
+
+    /// Writes the graph, or the part of it present in `window`, as
+    /// GraphML; see [`export::write_graphml`].
+    pub fn write_graphml<W: Write>(&self, writer: W, window: Option<(T, T)>) -> io::Result<()> {
+        export::write_graphml(self, writer, window)
+    }
+
+    /// Writes the graph, or the part of it present in `window`, as
+    /// dynamic GEXF; see [`export::write_gexf`].
+    pub fn write_gexf<W: Write>(&self, writer: W, window: Option<(T, T)>) -> io::Result<()> {
+        export::write_gexf(self, writer, window)
+    }
+
+    /// Applies a JSON Lines stream of graph events, in order; see
+    /// [`events::read_events`].
+    pub fn read_events<R: BufRead>(&mut self, reader: R, on_error: OnError) -> Result<EventReport, ImportError>
+    where
+        N: FromStr,
+        T: FromStr + TimeSpan,
+    {
+        events::read_events(self, reader, on_error)
+    }
+
+    /// Writes the JSON Lines events that rebuild the graph; see
+    /// [`events::write_events`].
+    pub fn write_events<W: Write>(&self, writer: W) -> io::Result<()> {
+        events::write_events(self, writer)
+    }
+
+    /// Computes the earliest time every node can be reached from `source`
+    /// leaving at `start_time`; see [`paths::earliest_arrival`].
+    pub fn earliest_arrival(&self, source: N, start_time: T) -> EarliestArrival<N, T>
+    where
+        T: TimeSpan,
+    {
+        paths::earliest_arrival(self, source, start_time)
+    }
+
+    /// Computes how late every node can be left to reach `target` by
+    /// `deadline`; see [`paths::latest_departure`].
+    pub fn latest_departure(&self, target: N, deadline: T) -> LatestDeparture<N, T>
+    where
+        T: TimeSpan,
+    {
+        paths::latest_departure(self, target, deadline)
+    }
+
+    /// Computes the minimum journey duration from `source` to every node;
+    /// see [`paths::fastest_paths`].
+    pub fn fastest_paths(&self, source: N, start_time: T) -> FastestPaths<N, T>
+    where
+        T: TimeSpan,
+    {
+        paths::fastest_paths(self, source, start_time)
+    }
+
+    /// Computes the fewest hops needed to reach every node from `source`;
+    /// see [`paths::fewest_hops`].
+    pub fn fewest_hops(&self, source: N, start_time: T) -> FewestHops<N, T>
+    where
+        T: TimeSpan,
+    {
+        paths::fewest_hops(self, source, start_time)
+    }
+
+    /// Returns the nodes `node` can reach within `[t_start, t_end)`; see
+    /// [`reachability::reachable_from`].
+    pub fn reachable_from(&self, node: N, t_start: T, t_end: T) -> HashSet<N>
+    where
+        T: TimeSpan,
+    {
+        reachability::reachable_from(self, node, t_start, t_end)
+    }
+
+    /// Computes the reachability relation within `[t_start, t_end)` from
+    /// every node; see [`reachability::reachability_matrix`].
+    pub fn reachability_matrix(&self, t_start: T, t_end: T) -> ReachabilityMatrix<N>
+    where
+        T: TimeSpan,
+    {
+        reachability::reachability_matrix(self, t_start, t_end)
+    }
+
+    /// Returns the weakly connected component id of every node alive at
+    /// `timestamp`; see [`components::weak_components_at`].
+    pub fn weak_components_at(&self, timestamp: T) -> HashMap<N, usize> {
+        components::weak_components_at(self, timestamp)
+    }
+
+    /// Returns the strongly temporally connected component id of every
+    /// node over `[t_start, t_end)`; see [`components::strong_temporal_components`].
+    pub fn strong_temporal_components(&self, t_start: T, t_end: T) -> HashMap<N, usize>
+    where
+        T: TimeSpan,
+    {
+        components::strong_temporal_components(self, t_start, t_end)
+    }
+
+    /// Counts the instances of every 3-edge δ-temporal motif; see
+    /// [`motifs::count_motifs`].
+    pub fn count_motifs(&self, delta: T) -> MotifCounts
+    where
+        T: TimeSpan,
+    {
+        motifs::count_motifs(self, delta)
+    }
+
+    /// Computes temporal PageRank over the edges that started by
+    /// `timestamp`; see [`pagerank::temporal_pagerank`].
+    pub fn temporal_pagerank(&self, timestamp: T, config: PageRankConfig) -> HashMap<N, f64> {
+        pagerank::temporal_pagerank(self, timestamp, config)
+    }
+
+    /// Returns the temporal closeness of every node over `[t_start, t_end)`;
+    /// see [`centrality::temporal_closeness`].
+    pub fn temporal_closeness(&self, t_start: T, t_end: T) -> HashMap<N, f64>
+    where
+        T: TimeSpan,
+    {
+        centrality::temporal_closeness(self, t_start, t_end)
+    }
+
+    /// Returns the temporal betweenness of every node over `[t_start, t_end)`;
+    /// see [`centrality::temporal_betweenness`].
+    pub fn temporal_betweenness(&self, t_start: T, t_end: T) -> HashMap<N, f64>
+    where
+        T: TimeSpan,
+    {
+        centrality::temporal_betweenness(self, t_start, t_end)
+    }
+
+    /// Returns the core number of every node present in `[t_start, t_end)`;
+    /// see [`cores::core_numbers`].
+    pub fn core_numbers(&self, t_start: T, t_end: T) -> HashMap<N, usize> {
+        cores::core_numbers(self, t_start, t_end)
+    }
+
+    /// Computes the core numbers in each of `windows`; see
+    /// [`cores::core_evolution`].
+    pub fn core_evolution(&self, windows: &[(T, T)]) -> CoreEvolution<N, T> {
+        cores::core_evolution(self, windows)
+    }
+}
+
+impl<N: NodeKey, T: Time> TemporalGraphOps for TemporalGraph<N, T> {
+    type Node = N;
+    type Time = T;
+
+    fn store(&self) -> &GraphStore<N, T> {
+        &self.store
+    }
+
+    fn store_mut(&mut self) -> &mut GraphStore<N, T> {
+        &mut self.store
+    }
+}
+
+impl<N: NodeKey + Codec, T: Time + Codec> Persistent for TemporalGraph<N, T> {
+    fn save_to<W: Write>(&self, writer: W) -> Result<(), PersistError> {
+        Self::save_to(self, writer)
+    }
+
+    fn load_from<R: Read>(reader: R) -> Result<Self, PersistError> {
+        Self::load_from(reader)
+    }
+}
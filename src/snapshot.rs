@@ -0,0 +1,165 @@
+//! src/snapshot.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module implements [`StaticGraph`], an immutable snapshot of a
+//! temporal graph at a time or over a window, stored in compressed
+//! sparse row (CSR) form for cheap queries and traversals.
+
+use std::collections::{HashMap, VecDeque};
+use crate::edge::TemporalEdge;
+use crate::types::{Directionality, NodeId, NodeKey, Time};
+
+/// An immutable static graph materialized from a temporal graph.
+///
+/// Nodes are mapped to dense indices `0..node_count()`. The neighbors of
+/// node `i` are `targets[offsets[i]..offsets[i + 1]]`, sorted by index.
+/// Parallel temporal edges between the same pair of nodes collapse into
+/// one static edge whose weight is the sum of their weights.
+#[derive(Debug, Clone)]
+pub struct StaticGraph<N = NodeId> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<f64>,
+    directionality: Directionality,
+}
+
+impl<N: NodeKey> StaticGraph<N> {
+    /// Assembles the CSR arrays from per-node `(target, weight)` lists,
+    /// one list per entry of `nodes`.
+    pub(crate) fn from_lists(
+        nodes: Vec<N>,
+        index: HashMap<N, usize>,
+        lists: Vec<Vec<(usize, f64)>>,
+        directionality: Directionality,
+    ) -> Self {
+        let mut offsets = Vec::with_capacity(nodes.len() + 1);
+        offsets.push(0);
+        for list in &lists {
+            offsets.push(offsets[offsets.len() - 1] + list.len());
+        }
+        let (targets, weights) = lists.into_iter().flatten().unzip();
+        Self {
+            nodes,
+            index,
+            offsets,
+            targets,
+            weights,
+            directionality,
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the number of static edges, counting each undirected edge once.
+    pub fn edge_count(&self) -> usize {
+        match self.directionality {
+            Directionality::Directed => self.targets.len(),
+            Directionality::Undirected => {
+                let self_loops = (0..self.node_count())
+                    .filter(|&i| self.neighbors(i).binary_search(&i).is_ok())
+                    .count();
+                (self.targets.len() + self_loops) / 2
+            }
+        }
+    }
+
+    pub fn directionality(&self) -> Directionality {
+        self.directionality
+    }
+
+    /// Returns the node keys in index order.
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// Returns the node key at `index`.
+    pub fn node(&self, index: usize) -> &N {
+        &self.nodes[index]
+    }
+
+    /// Returns the index of `node`, or `None` if it is not in the snapshot.
+    pub fn index_of(&self, node: &N) -> Option<usize> {
+        self.index.get(node).copied()
+    }
+
+    /// Returns the neighbor indices of the node at `index`.
+    pub fn neighbors(&self, index: usize) -> &[usize] {
+        &self.targets[self.offsets[index]..self.offsets[index + 1]]
+    }
+
+    /// Returns the edge weights aligned with [`neighbors`](Self::neighbors).
+    pub fn neighbor_weights(&self, index: usize) -> &[f64] {
+        &self.weights[self.offsets[index]..self.offsets[index + 1]]
+    }
+
+    /// Returns the neighbor keys of `node`.
+    pub fn neighbors_of(&self, node: &N) -> Vec<&N> {
+        self.index_of(node)
+            .map(|i| self.neighbors(i).iter().map(|&j| &self.nodes[j]).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn degree(&self, index: usize) -> usize {
+        self.offsets[index + 1] - self.offsets[index]
+    }
+
+    pub fn has_edge(&self, from: usize, to: usize) -> bool {
+        self.neighbors(from).binary_search(&to).is_ok()
+    }
+
+    /// Returns the node indices reachable from `source` in breadth-first order.
+    pub fn bfs(&self, source: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.node_count()];
+        let mut order = Vec::new();
+        let mut queue = VecDeque::from([source]);
+        visited[source] = true;
+        while let Some(current) = queue.pop_front() {
+            order.push(current);
+            for &next in self.neighbors(current) {
+                if !visited[next] {
+                    visited[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        order
+    }
+}
+
+/// Maps each node to its position in `nodes`.
+pub(crate) fn index_nodes<N: NodeKey>(nodes: &[N]) -> HashMap<N, usize> {
+    nodes.iter().enumerate().map(|(i, node)| (node.clone(), i)).collect()
+}
+
+/// Collects the static neighbor list of one node from its temporal edges:
+/// edges passing `keep` whose destination is in `index`, merged per
+/// destination with summed weights and sorted by destination index.
+pub(crate) fn collect_targets<N: NodeKey, T: Time>(
+    edges: &[TemporalEdge<N, T>],
+    index: &HashMap<N, usize>,
+    keep: impl Fn(&TemporalEdge<N, T>) -> bool,
+) -> Vec<(usize, f64)> {
+    let mut targets: Vec<(usize, f64)> = edges
+        .iter()
+        .filter(|edge| keep(edge))
+        .filter_map(|edge| index.get(&edge.dst).map(|&j| (j, edge.weight)))
+        .collect();
+    targets.sort_by_key(|&(j, _)| j);
+    targets.dedup_by(|next, kept| {
+        if next.0 == kept.0 {
+            kept.1 += next.1;
+            true
+        } else {
+            false
+        }
+    });
+    targets
+}
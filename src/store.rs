@@ -0,0 +1,452 @@
+//! src/store.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module defines [`GraphStore`], the nodes, lifetimes and sorted
+//! adjacency lists behind both
+//! [`TemporalGraph`](crate::sequential::TemporalGraph) and
+//! [`ChronoGraph`](crate::chrono::ChronoGraph). It holds every mutation
+//! and the sequential version of every query; the provided methods of
+//! [`TemporalGraphOps`](crate::ops::TemporalGraphOps) call into it, and
+//! `ChronoGraph` overrides the queries it runs in parallel.
+
+use std::collections::{HashMap, HashSet};
+use crate::edge::{insert_sorted, reverse_adjacency, started_before, started_by, AdjacencyMap, Interval, Lifetime, Properties, TemporalEdge};
+use crate::error::{GraphError, PersistError};
+use crate::persist::GraphImage;
+use crate::snapshot::{collect_targets, index_nodes, StaticGraph};
+use crate::types::{Directionality, NodeKey, Time, TimeSpan};
+
+/// The storage shared by both graph backends.
+///
+/// It is only reachable through
+/// [`TemporalGraphOps::store`](crate::ops::TemporalGraphOps::store) and
+/// has no public methods of its own.
+#[derive(Debug, Clone)]
+pub struct GraphStore<N, T> {
+    // Adjacency lists are kept sorted by interval start.
+    pub(crate) edges: AdjacencyMap<N, T>,
+    // Optional reverse adjacency: `in_edges[dst]` holds one entry per
+    // incoming edge, with the entry's `dst` field set to the source
+    // (properties are only kept on the outgoing entry).
+    pub(crate) in_edges: Option<AdjacencyMap<N, T>>,
+    pub(crate) nodes: HashSet<N>,
+    // Nodes without an entry exist at all times.
+    pub(crate) lifetimes: HashMap<N, Lifetime<T>>,
+    // Undirected graphs store every edge under both endpoints.
+    pub(crate) directionality: Directionality,
+}
+
+impl<N: NodeKey, T: Time> GraphStore<N, T> {
+    pub(crate) fn new(directionality: Directionality) -> Self {
+        Self {
+            edges: HashMap::new(),
+            in_edges: None,
+            nodes: HashSet::new(),
+            lifetimes: HashMap::new(),
+            directionality,
+        }
+    }
+
+    /// Rebuilds the storage from a decoded graph file, reporting edges
+    /// that do not fit its nodes as corruption.
+    pub(crate) fn from_image(image: GraphImage<N, T>) -> Result<Self, PersistError> {
+        let mut store = Self::new(image.directionality);
+        for (node, lifetime) in image.nodes {
+            if let Some(lifetime) = lifetime {
+                store.lifetimes.insert(node.clone(), lifetime);
+            }
+            store.nodes.insert(node);
+        }
+        for (src, edge) in image.edges {
+            store.insert_edge(src, edge).map_err(|err| PersistError::Corrupt(err.to_string()))?;
+        }
+        if image.in_index {
+            store.enable_in_index();
+        }
+        Ok(store)
+    }
+
+    pub(crate) fn is_directed(&self) -> bool {
+        self.directionality == Directionality::Directed
+    }
+
+    pub(crate) fn enable_in_index(&mut self) {
+        if self.is_directed() && self.in_edges.is_none() {
+            self.in_edges = Some(reverse_adjacency(&self.edges));
+        }
+    }
+
+    pub(crate) fn add_node(&mut self, node_id: N) {
+        self.nodes.insert(node_id);
+    }
+
+    pub(crate) fn add_node_at(&mut self, node_id: N, timestamp: T) {
+        self.lifetimes.insert(node_id.clone(), Lifetime::starting_at(timestamp));
+        self.nodes.insert(node_id);
+    }
+
+    pub(crate) fn remove_node_at(&mut self, node_id: N, timestamp: T) -> Result<N, GraphError<N, T>> {
+        if !self.nodes.contains(&node_id) {
+            return Err(GraphError::NodeNotFound(node_id));
+        }
+        let lifetime = self.lifetimes.entry(node_id.clone()).or_default();
+        if let Some(born) = lifetime.born {
+            if timestamp <= born {
+                return Err(GraphError::InvalidInterval { start: born, end: timestamp });
+            }
+        }
+        lifetime.died = Some(timestamp);
+        Ok(node_id)
+    }
+
+    pub(crate) fn add_edge(&mut self, src: N, dst: N, interval: Interval<T>) -> Result<(), GraphError<N, T>> {
+        check_interval(&interval)?;
+        self.insert_edge(src, TemporalEdge::new(dst, interval))
+    }
+
+    pub(crate) fn add_edge_with(&mut self, src: N, dst: N, interval: Interval<T>, weight: f64, properties: Properties) -> Result<(), GraphError<N, T>> {
+        check_interval(&interval)?;
+        let mut edge = TemporalEdge::new(dst, interval).with_weight(weight);
+        edge.properties = properties;
+        self.insert_edge(src, edge)
+    }
+
+    pub(crate) fn add_edge_with_duration(&mut self, src: N, dst: N, interval: Interval<T>, duration: T) -> Result<(), GraphError<N, T>>
+    where
+        T: TimeSpan,
+    {
+        check_interval(&interval)?;
+        if duration < T::ZERO {
+            return Err(GraphError::InvalidDuration(duration));
+        }
+        self.insert_edge(src, TemporalEdge::new(dst, interval).with_duration(duration))
+    }
+
+    pub(crate) fn insert_edge(&mut self, src: N, edge: TemporalEdge<N, T>) -> Result<(), GraphError<N, T>> {
+        if !self.nodes.contains(&src) {
+            return Err(GraphError::MissingSource(src));
+        }
+        if !self.nodes.contains(&edge.dst) {
+            return Err(GraphError::MissingDestination(edge.dst));
+        }
+        if !self.is_directed() {
+            if src != edge.dst {
+                let mut mirror = edge.clone();
+                mirror.dst = src.clone();
+                mirror.mirror = true;
+                insert_sorted(self.edges.entry(edge.dst.clone()).or_default(), mirror);
+            }
+        } else if let Some(in_edges) = &mut self.in_edges {
+            insert_sorted(in_edges.entry(edge.dst.clone()).or_default(), edge.reversed(src.clone()));
+        }
+        insert_sorted(self.edges.entry(src).or_default(), edge);
+        Ok(())
+    }
+
+    /// Removes `node_id` with its edges, visiting only its own neighbors
+    /// unless the graph is directed without an incoming-edge index.
+    pub(crate) fn remove_node(&mut self, node_id: N) -> Result<N, GraphError<N, T>> {
+        if !self.nodes.remove(&node_id) {
+            return Err(GraphError::NodeNotFound(node_id));
+        }
+        self.lifetimes.remove(&node_id);
+
+        let outgoing = self.edges.remove(&node_id).unwrap_or_default();
+
+        if !self.is_directed() {
+            for target in outgoing.iter().map(|edge| &edge.dst).collect::<HashSet<_>>() {
+                if let Some(neighbors) = self.edges.get_mut(target) {
+                    neighbors.retain(|edge| edge.dst != node_id);
+                }
+            }
+            return Ok(node_id);
+        }
+
+        match &mut self.in_edges {
+            Some(in_edges) => {
+                let incoming = in_edges.remove(&node_id).unwrap_or_default();
+                for source in incoming.iter().map(|edge| &edge.dst).collect::<HashSet<_>>() {
+                    if let Some(neighbors) = self.edges.get_mut(source) {
+                        neighbors.retain(|edge| edge.dst != node_id);
+                    }
+                }
+                for target in outgoing.iter().map(|edge| &edge.dst).collect::<HashSet<_>>() {
+                    if let Some(sources) = in_edges.get_mut(target) {
+                        sources.retain(|edge| edge.dst != node_id);
+                    }
+                }
+            }
+            None => {
+                for neighbors in self.edges.values_mut() {
+                    neighbors.retain(|edge| edge.dst != node_id);
+                }
+            }
+        }
+
+        Ok(node_id)
+    }
+
+    pub(crate) fn remove_edge(&mut self, src: N, dst: N, timestamp: T) -> Result<(N, N, T), GraphError<N, T>> {
+        if let Some(neighbors) = self.edges.get_mut(&src) {
+            let before = neighbors.len();
+            neighbors.retain(|edge| !(edge.dst == dst && edge.interval.start == timestamp));
+            if neighbors.len() < before {
+                if !self.is_directed() {
+                    if src != dst {
+                        if let Some(mirror) = self.edges.get_mut(&dst) {
+                            mirror.retain(|edge| !(edge.dst == src && edge.interval.start == timestamp));
+                        }
+                    }
+                } else if let Some(sources) = self.in_edges.as_mut().and_then(|in_edges| in_edges.get_mut(&dst)) {
+                    sources.retain(|edge| !(edge.dst == src && edge.interval.start == timestamp));
+                }
+                return Ok((src, dst, timestamp));
+            }
+        }
+        Err(GraphError::EdgeNotFound { src, dst, timestamp })
+    }
+
+    pub(crate) fn get_neighbors_at(&self, node: &N, timestamp: T) -> Vec<N> {
+        self.get_neighbors_at_filtered(node, timestamp, |_| true)
+    }
+
+    pub(crate) fn get_edges_at(&self, node: &N, timestamp: T) -> Vec<&TemporalEdge<N, T>> {
+        self.started_edges(&self.edges, node, timestamp)
+            .iter()
+            .filter(|edge| self.is_visible(edge, timestamp))
+            .collect()
+    }
+
+    pub(crate) fn get_neighbors_at_filtered<F>(&self, node: &N, timestamp: T, filter: F) -> Vec<N>
+    where
+        F: Fn(&TemporalEdge<N, T>) -> bool,
+    {
+        self.started_edges(&self.edges, node, timestamp)
+            .iter()
+            .filter(|edge| self.is_visible(edge, timestamp) && filter(edge))
+            .map(|edge| edge.dst.clone())
+            .collect()
+    }
+
+    /// Scans for the sources of edges into `node` of a directed graph.
+    pub(crate) fn get_in_neighbors_at(&self, node: &N, timestamp: T) -> Vec<N> {
+        match &self.in_edges {
+            Some(in_edges) => self
+                .started_edges(in_edges, node, timestamp)
+                .iter()
+                .filter(|edge| self.is_visible(edge, timestamp))
+                .map(|edge| edge.dst.clone())
+                .collect(),
+            None if !self.is_alive_at(node, timestamp) => Vec::new(),
+            None => self
+                .edges
+                .iter()
+                .filter(|(src, _)| self.within_lifetime(src, timestamp))
+                .flat_map(|(src, neighbors)| {
+                    started_by(neighbors, timestamp)
+                        .iter()
+                        .filter(|edge| &edge.dst == node && edge.is_active_at(timestamp))
+                        .map(move |_| src.clone())
+                })
+                .collect(),
+        }
+    }
+
+    pub(crate) fn get_neighbors_between(&self, node: &N, t_start: T, t_end: T) -> Vec<N> {
+        let edges = match self.edges.get(node) {
+            Some(edges) if self.overlaps_lifetime(node, t_start, t_end) => edges,
+            _ => return Vec::new(),
+        };
+        let mut seen = HashSet::new();
+        started_before(edges, t_end)
+            .iter()
+            .filter(|edge| edge.interval.overlaps(t_start, t_end) && self.overlaps_lifetime(&edge.dst, t_start, t_end))
+            .filter(|edge| seen.insert(&edge.dst))
+            .map(|edge| edge.dst.clone())
+            .collect()
+    }
+
+    pub(crate) fn edges_in_range(&self, t_start: T, t_end: T) -> Vec<(N, &TemporalEdge<N, T>)> {
+        self.edges
+            .iter()
+            .flat_map(|(src, edges)| self.edges_from_in_range(src, edges, t_start, t_end))
+            .collect()
+    }
+
+    /// Returns the edges of `src` listed by [`edges_in_range`](Self::edges_in_range).
+    pub(crate) fn edges_from_in_range<'a>(
+        &'a self,
+        src: &'a N,
+        edges: &'a [TemporalEdge<N, T>],
+        t_start: T,
+        t_end: T,
+    ) -> impl Iterator<Item = (N, &'a TemporalEdge<N, T>)> + 'a {
+        let edges = if self.overlaps_lifetime(src, t_start, t_end) { started_before(edges, t_end) } else { &[] };
+        edges
+            .iter()
+            .filter(move |edge| !edge.mirror && edge.interval.overlaps(t_start, t_end) && self.overlaps_lifetime(&edge.dst, t_start, t_end))
+            .map(move |edge| (src.clone(), edge))
+    }
+
+    pub(crate) fn first_contact(&self, src: &N, dst: &N) -> Option<T> {
+        self.edges
+            .get(src)?
+            .iter()
+            .filter(|edge| &edge.dst == dst)
+            .find_map(|edge| self.first_visible(src, edge))
+    }
+
+    pub(crate) fn last_contact(&self, src: &N, dst: &N) -> Option<T> {
+        self.edges
+            .get(src)?
+            .iter()
+            .rev()
+            .filter(|edge| &edge.dst == dst)
+            .find_map(|edge| self.first_visible(src, edge))
+    }
+
+    pub(crate) fn snapshot_at(&self, timestamp: T) -> StaticGraph<N> {
+        let nodes = self.nodes_at(timestamp);
+        let index = index_nodes(&nodes);
+        let lists = nodes
+            .iter()
+            .map(|node| {
+                let edges = self.started_edges(&self.edges, node, timestamp);
+                collect_targets(edges, &index, |edge| edge.is_active_at(timestamp))
+            })
+            .collect();
+        StaticGraph::from_lists(nodes, index, lists, self.directionality)
+    }
+
+    pub(crate) fn snapshot_between(&self, t_start: T, t_end: T) -> StaticGraph<N> {
+        let nodes: Vec<N> = self
+            .nodes
+            .iter()
+            .filter(|node| self.overlaps_lifetime(node, t_start, t_end))
+            .cloned()
+            .collect();
+        let index = index_nodes(&nodes);
+        let lists = nodes
+            .iter()
+            .map(|node| {
+                let edges = self.edges.get(node).map(|edges| started_before(edges, t_end)).unwrap_or(&[]);
+                collect_targets(edges, &index, |edge| edge.interval.overlaps(t_start, t_end))
+            })
+            .collect();
+        StaticGraph::from_lists(nodes, index, lists, self.directionality)
+    }
+
+    pub(crate) fn edge_count(&self) -> usize {
+        let entries: usize = self.edges.values().map(Vec::len).sum();
+        if self.is_directed() {
+            return entries;
+        }
+        // Undirected self-loops are the only edges stored a single time.
+        let self_loops: usize = self
+            .edges
+            .iter()
+            .map(|(node, edges)| edges.iter().filter(|edge| &edge.dst == node).count())
+            .sum();
+        (entries + self_loops) / 2
+    }
+
+    pub(crate) fn out_degree(&self, node: &N) -> usize {
+        self.edges.get(node).map_or(0, Vec::len)
+    }
+
+    /// Counts the edges into `node` of a directed graph.
+    pub(crate) fn in_degree(&self, node: &N) -> usize {
+        match &self.in_edges {
+            Some(in_edges) => in_edges.get(node).map_or(0, Vec::len),
+            None => self
+                .edges
+                .values()
+                .map(|edges| edges.iter().filter(|edge| &edge.dst == node).count())
+                .sum(),
+        }
+    }
+
+    pub(crate) fn is_alive_at(&self, node: &N, timestamp: T) -> bool {
+        self.nodes.contains(node) && self.within_lifetime(node, timestamp)
+    }
+
+    /// Lifetime check alone, for nodes already known to exist.
+    pub(crate) fn within_lifetime(&self, node: &N, timestamp: T) -> bool {
+        self.lifetimes.is_empty() || self.lifetimes.get(node).is_none_or(|lifetime| lifetime.contains(timestamp))
+    }
+
+    pub(crate) fn node_lifetime(&self, node: &N) -> Option<Lifetime<T>> {
+        if !self.nodes.contains(node) {
+            return None;
+        }
+        Some(self.lifetimes.get(node).copied().unwrap_or_default())
+    }
+
+    pub(crate) fn nodes_at(&self, timestamp: T) -> Vec<N> {
+        self.nodes
+            .iter()
+            .filter(|node| self.is_alive_at(node, timestamp))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the edges of `node` in `adjacency` that started by
+    /// `timestamp`, or nothing if `node` is not alive then.
+    pub(crate) fn started_edges<'a>(&self, adjacency: &'a AdjacencyMap<N, T>, node: &N, timestamp: T) -> &'a [TemporalEdge<N, T>] {
+        if !self.is_alive_at(node, timestamp) {
+            return &[];
+        }
+        adjacency
+            .get(node)
+            .map(|edges| started_by(edges, timestamp))
+            .unwrap_or(&[])
+    }
+
+    /// Returns `true` if an existing `node` is alive at some point within
+    /// `[t_start, t_end)`.
+    pub(crate) fn overlaps_lifetime(&self, node: &N, t_start: T, t_end: T) -> bool {
+        t_start < t_end && self.lifetimes.get(node).is_none_or(|lifetime| lifetime.overlaps(t_start, t_end))
+    }
+
+    /// Returns the first time `edge` out of `src` is active while both its
+    /// endpoints are alive, if there is one.
+    fn first_visible(&self, src: &N, edge: &TemporalEdge<N, T>) -> Option<T> {
+        let lifetime = |node| self.lifetimes.get(node).copied().unwrap_or_default();
+        let (src_lifetime, dst_lifetime) = (lifetime(src), lifetime(&edge.dst));
+        let from = [src_lifetime.born, dst_lifetime.born].into_iter().flatten().fold(edge.interval.start, T::max);
+        (edge.interval.contains(from) && src_lifetime.contains(from) && dst_lifetime.contains(from)).then_some(from)
+    }
+
+    /// An edge is visible when it is active and its far endpoint is alive.
+    pub(crate) fn is_visible(&self, edge: &TemporalEdge<N, T>, timestamp: T) -> bool {
+        edge.is_active_at(timestamp) && self.within_lifetime(&edge.dst, timestamp)
+    }
+
+    pub(crate) fn print(&self) {
+        for node in &self.nodes {
+            match self.lifetimes.get(node) {
+                Some(lifetime) => println!("Node {} (alive {}):", node, lifetime),
+                None => println!("Node {}:", node),
+            }
+            if let Some(edges) = self.edges.get(node) {
+                for edge in edges {
+                    println!("  {}", edge);
+                }
+            } else {
+                println!("  (no outgoing edges)");
+            }
+        }
+    }
+}
+
+/// Rejects a bounded interval that ends at or before its start.
+fn check_interval<N, T: Time>(interval: &Interval<T>) -> Result<(), GraphError<N, T>> {
+    match interval.end {
+        Some(end) if end <= interval.start => Err(GraphError::InvalidInterval { start: interval.start, end }),
+        _ => Ok(()),
+    }
+}
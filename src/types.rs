@@ -0,0 +1,86 @@
+//! src/types.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module defines the node and timestamp types the graphs are
+//! generic over, together with the default types used when none are given.
+
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+
+/// Default node identifier: a plain integer id.
+pub type NodeId = usize;
+
+/// Default timestamp: a non-negative integer.
+pub type Timestamp = u64;
+
+/// Requirements on node identifiers.
+///
+/// Any hashable, cloneable and printable type qualifies, e.g. integer
+/// ids, `String` user names or UUIDs.
+pub trait NodeKey: Hash + Eq + Clone + Debug + Display {}
+
+impl<K: Hash + Eq + Clone + Debug + Display> NodeKey for K {}
+
+/// Requirements on timestamps.
+///
+/// Any totally ordered, copyable and printable type qualifies, e.g.
+/// `u64` seconds, `i64` nanoseconds or signed pre-epoch times.
+pub trait Time: Ord + Copy + Debug + Display {}
+
+impl<T: Ord + Copy + Debug + Display> Time for T {}
+
+/// Timestamps that can also measure durations, as needed by path
+/// algorithms over edges that take time to traverse.
+///
+/// Implemented for the primitive integer types, where `STEP` is the
+/// smallest gap between two distinct times.
+pub trait TimeSpan: Time {
+    const ZERO: Self;
+    const STEP: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    /// Converts the time to `f64`, rounding to the nearest representable value.
+    fn as_f64(self) -> f64;
+}
+
+macro_rules! impl_time_span {
+    ($($t:ty),*) => {
+        $(
+            impl TimeSpan for $t {
+                const ZERO: Self = 0;
+                const STEP: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn as_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_time_span!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Whether edges are one-way or symmetric, fixed when a graph is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Directionality {
+    /// `add_edge(a, b, t)` makes `b` a neighbor of `a` only.
+    #[default]
+    Directed,
+    /// `add_edge(a, b, t)` makes `a` and `b` neighbors of each other.
+    Undirected,
+}
@@ -0,0 +1,526 @@
+//! src/wal.rs
+//!
+//! # Author
+//! Yifan Jiang, 2025
+//! alvinjiang01@hotmail.com
+//! GitHub: [AlvinJ404](https://github.com/AlvinJ404/chronograph)
+//!
+//! This module implements [`DurableGraph`], which keeps a graph in a
+//! directory on disk: every mutation is appended to a write-ahead log
+//! before it is applied, and checkpoints store the whole graph in the
+//! [`persist`] format so the log can start over.
+//!
+//! The directory holds two files:
+//!
+//! ```text
+//! checkpoint.bin = magic "CHCK" | lsn: u64 LE | crc32: u32 LE | graph file
+//! wal.log        = magic "CHWL" | version: u8 | record*
+//! record         = length: u32 LE | crc32: u32 LE | lsn: u64 LE | payload
+//! ```
+//!
+//! Every record carries a log sequence number (LSN), and its checksum
+//! covers the LSN and payload. The checkpoint records the LSN of the last
+//! mutation it contains, so records already in it are skipped on replay.
+//! Replay stops at the first incomplete or damaged record, which a crash
+//! in the middle of an append leaves behind, and the log is cut there.
+//!
+//! An append that fails is cut off again right away, so the records
+//! logged after it stay readable. If even that fails, the graph refuses
+//! further mutations until it is reopened.
+//!
+//! A mutation is committed once its record is logged, so a failed
+//! automatic checkpoint after it does not fail the mutation. The error is
+//! kept for [`DurableGraph::take_checkpoint_error`] and the checkpoint is
+//! tried again with the next mutation.
+
+use std::fmt::Debug;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use crate::edge::{Interval, Properties, TemporalEdge};
+use crate::error::{GraphError, PersistError, WalError};
+use crate::ops::TemporalGraphOps;
+use crate::persist::{self, crc32, Codec, Persistent};
+use crate::types::{NodeKey, TimeSpan};
+
+const CHECKPOINT_FILE: &str = "checkpoint.bin";
+const LOG_FILE: &str = "wal.log";
+const CHECKPOINT_MAGIC: [u8; 4] = *b"CHCK";
+const LOG_MAGIC: [u8; 4] = *b"CHWL";
+const LOG_VERSION: u8 = 1;
+const LOG_HEADER_LEN: usize = LOG_MAGIC.len() + 1;
+const RECORD_HEADER_LEN: usize = 16;
+
+/// The file the log is appended to.
+pub(crate) trait LogFile: Write + Debug + Send + Sync {
+    fn sync_data(&self) -> io::Result<()>;
+
+    fn set_len(&self, len: u64) -> io::Result<()>;
+}
+
+impl LogFile for File {
+    fn sync_data(&self) -> io::Result<()> {
+        File::sync_data(self)
+    }
+
+    fn set_len(&self, len: u64) -> io::Result<()> {
+        File::set_len(self, len)
+    }
+}
+
+/// A graph whose mutations are logged to disk before they are applied.
+///
+/// Queries go through [`graph`](Self::graph). Each mutation is synced to
+/// disk before it returns unless [`set_sync_on_write`](Self::set_sync_on_write)
+/// turns that off, in which case [`sync`](Self::sync) flushes the log.
+#[derive(Debug)]
+pub struct DurableGraph<G> {
+    graph: G,
+    dir: PathBuf,
+    log: Box<dyn LogFile>,
+    // Length of the log up to the end of its last complete record.
+    log_len: u64,
+    // Set when a failed append could not be undone, leaving the log
+    // unfit for further records.
+    poisoned: bool,
+    // LSN of the last logged mutation, `0` before the first.
+    lsn: u64,
+    // LSN of the last mutation contained in the checkpoint.
+    checkpoint_lsn: u64,
+    // Checkpoint automatically once this many mutations follow the last one.
+    checkpoint_every: Option<u64>,
+    // Why the last automatic checkpoint failed, until taken.
+    checkpoint_error: Option<PersistError>,
+    sync_on_write: bool,
+}
+
+impl<G> DurableGraph<G>
+where
+    G: Persistent,
+    G::Node: Codec,
+    G::Time: Codec + TimeSpan,
+{
+    /// Opens the graph stored in `dir`, creating the directory and an
+    /// empty default graph if there is none yet.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, PersistError>
+    where
+        G: Default,
+    {
+        Self::open_with(dir, G::default)
+    }
+
+    /// Opens the graph stored in `dir`, starting from `empty()` if the
+    /// directory holds no checkpoint yet, e.g. to pick an undirected graph.
+    ///
+    /// Loads the checkpoint, then replays the log records after it.
+    pub fn open_with(dir: impl AsRef<Path>, empty: impl FnOnce() -> G) -> Result<Self, PersistError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        let (mut graph, checkpoint_lsn) = match File::open(dir.join(CHECKPOINT_FILE)) {
+            Ok(file) => read_checkpoint(file)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => (empty(), 0),
+            Err(err) => return Err(err.into()),
+        };
+
+        let log_path = dir.join(LOG_FILE);
+        let bytes = match fs::read(&log_path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err.into()),
+        };
+        let (lsn, valid_len) = if bytes.len() < LOG_HEADER_LEN && LOG_MAGIC.starts_with(&bytes[..bytes.len().min(LOG_MAGIC.len())]) {
+            // A missing log, or one torn while its header was written.
+            write_new_log(&dir)?;
+            (checkpoint_lsn, LOG_HEADER_LEN)
+        } else {
+            replay(&bytes, checkpoint_lsn, &mut graph)?
+        };
+
+        let log = OpenOptions::new().append(true).open(&log_path)?;
+        if (valid_len as u64) < log.metadata()?.len() {
+            log.set_len(valid_len as u64)?;
+            log.sync_all()?;
+        }
+        Ok(Self {
+            graph,
+            dir,
+            log: Box::new(log),
+            log_len: valid_len as u64,
+            poisoned: false,
+            lsn,
+            checkpoint_lsn,
+            checkpoint_every: None,
+            checkpoint_error: None,
+            sync_on_write: true,
+        })
+    }
+
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// Returns the in-memory graph, closing the log.
+    pub fn into_inner(self) -> G {
+        self.graph
+    }
+
+    /// Returns the LSN of the last logged mutation.
+    pub fn lsn(&self) -> u64 {
+        self.lsn
+    }
+
+    /// Returns the LSN of the last mutation contained in the checkpoint.
+    pub fn checkpoint_lsn(&self) -> u64 {
+        self.checkpoint_lsn
+    }
+
+    /// Checkpoints automatically once `every` mutations have been logged
+    /// since the last checkpoint, or never if `None` (the default).
+    pub fn set_checkpoint_interval(&mut self, every: Option<u64>) {
+        self.checkpoint_every = every.filter(|&every| every > 0);
+    }
+
+    /// Returns why the last automatic checkpoint failed, if it did, and
+    /// clears it. The mutation that triggered it was committed anyway,
+    /// and the checkpoint is tried again with the next mutation.
+    pub fn take_checkpoint_error(&mut self) -> Option<PersistError> {
+        self.checkpoint_error.take()
+    }
+
+    /// Chooses whether every mutation is synced to disk before it returns
+    /// (the default). Without it a crash can lose the latest mutations.
+    pub fn set_sync_on_write(&mut self, sync: bool) {
+        self.sync_on_write = sync;
+    }
+
+    /// Syncs the log to disk.
+    pub fn sync(&mut self) -> Result<(), PersistError> {
+        self.log.sync_data()?;
+        Ok(())
+    }
+
+    /// Returns `true` if a failed write left the log in a state no
+    /// further records can follow, so every mutation fails until the
+    /// graph is reopened.
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_log_file(&mut self, log: Box<dyn LogFile>) {
+        self.log = log;
+    }
+
+    /// Writes the whole graph to the checkpoint file and starts a new,
+    /// empty log.
+    pub fn checkpoint(&mut self) -> Result<(), PersistError> {
+        self.check_poisoned()?;
+        let tmp = self.dir.join(format!("{}.tmp", CHECKPOINT_FILE));
+        let mut writer = BufWriter::new(File::create(&tmp)?);
+        let mut header = CHECKPOINT_MAGIC.to_vec();
+        header.extend_from_slice(&self.lsn.to_le_bytes());
+        let checksum = crc32(&header);
+        header.extend_from_slice(&checksum.to_le_bytes());
+        writer.write_all(&header)?;
+        self.graph.save_to(&mut writer)?;
+        writer.into_inner().map_err(|err| err.into_error())?.sync_all()?;
+        fs::rename(&tmp, self.dir.join(CHECKPOINT_FILE))?;
+        sync_dir(&self.dir)?;
+
+        // Records up to `self.lsn` are now in the checkpoint, so a crash
+        // before the log is replaced only replays records it skips. If
+        // replacing it fails, the open log may no longer be the one in
+        // the directory.
+        let log = write_new_log(&self.dir).and_then(|()| Ok(OpenOptions::new().append(true).open(self.dir.join(LOG_FILE))?));
+        match log {
+            Ok(log) => {
+                self.log = Box::new(log);
+                self.log_len = LOG_HEADER_LEN as u64;
+                self.checkpoint_lsn = self.lsn;
+                Ok(())
+            }
+            Err(err) => {
+                self.poisoned = true;
+                Err(err)
+            }
+        }
+    }
+
+    pub fn add_node(&mut self, node_id: G::Node) -> Result<(), WalError<G::Node, G::Time>> {
+        self.commit(Record::AddNode(node_id))
+    }
+
+    pub fn add_node_at(&mut self, node_id: G::Node, timestamp: G::Time) -> Result<(), WalError<G::Node, G::Time>> {
+        self.commit(Record::AddNodeAt(node_id, timestamp))
+    }
+
+    pub fn remove_node_at(&mut self, node_id: G::Node, timestamp: G::Time) -> Result<G::Node, WalError<G::Node, G::Time>> {
+        self.commit(Record::RemoveNodeAt(node_id.clone(), timestamp))?;
+        Ok(node_id)
+    }
+
+    pub fn add_edge(&mut self, src: G::Node, dst: G::Node, timestamp: G::Time) -> Result<(), WalError<G::Node, G::Time>> {
+        self.commit(Record::AddEdge(src, TemporalEdge::new(dst, Interval::starting_at(timestamp))))
+    }
+
+    pub fn add_edge_interval(&mut self, src: G::Node, dst: G::Node, start: G::Time, end: G::Time) -> Result<(), WalError<G::Node, G::Time>> {
+        self.commit(Record::AddEdge(src, TemporalEdge::new(dst, Interval { start, end: Some(end) })))
+    }
+
+    pub fn add_edge_with(
+        &mut self,
+        src: G::Node,
+        dst: G::Node,
+        interval: Interval<G::Time>,
+        weight: f64,
+        properties: Properties,
+    ) -> Result<(), WalError<G::Node, G::Time>> {
+        let mut edge = TemporalEdge::new(dst, interval).with_weight(weight);
+        edge.properties = properties;
+        self.commit(Record::AddEdge(src, edge))
+    }
+
+    pub fn add_edge_with_duration(
+        &mut self,
+        src: G::Node,
+        dst: G::Node,
+        interval: Interval<G::Time>,
+        duration: G::Time,
+    ) -> Result<(), WalError<G::Node, G::Time>> {
+        self.commit(Record::AddEdge(src, TemporalEdge::new(dst, interval).with_duration(duration)))
+    }
+
+    pub fn remove_node(&mut self, node_id: G::Node) -> Result<G::Node, WalError<G::Node, G::Time>> {
+        self.commit(Record::RemoveNode(node_id.clone()))?;
+        Ok(node_id)
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn remove_edge(&mut self, src: G::Node, dst: G::Node, timestamp: G::Time) -> Result<(G::Node, G::Node, G::Time), WalError<G::Node, G::Time>> {
+        self.commit(Record::RemoveEdge(src.clone(), dst.clone(), timestamp))?;
+        Ok((src, dst, timestamp))
+    }
+
+    pub fn enable_in_index(&mut self) -> Result<(), WalError<G::Node, G::Time>> {
+        self.commit(Record::EnableInIndex)
+    }
+
+    /// Logs `record`, applies it, and checkpoints if the interval is due.
+    /// The result is the mutation's alone: a failed checkpoint is kept
+    /// for [`take_checkpoint_error`](Self::take_checkpoint_error).
+    fn commit(&mut self, record: Record<G::Node, G::Time>) -> Result<(), WalError<G::Node, G::Time>> {
+        self.check_poisoned()?;
+        let mut payload = Vec::new();
+        record.encode(&mut payload);
+        let lsn = self.lsn + 1;
+        let frame = frame(lsn, &payload);
+        if let Err(err) = self.append(&frame) {
+            // Cut off whatever part of the record reached the file, so
+            // that later records are not stranded behind it on replay.
+            if self.log.set_len(self.log_len).is_err() {
+                self.poisoned = true;
+            }
+            return Err(err.into());
+        }
+        self.log_len += frame.len() as u64;
+        self.lsn = lsn;
+
+        let result = record.apply(&mut self.graph);
+        if self.checkpoint_every.is_some_and(|every| self.lsn - self.checkpoint_lsn >= every) {
+            self.checkpoint_error = self.checkpoint().err();
+        }
+        Ok(result?)
+    }
+
+    fn append(&mut self, frame: &[u8]) -> io::Result<()> {
+        self.log.write_all(frame)?;
+        if self.sync_on_write {
+            self.log.sync_data()?;
+        }
+        Ok(())
+    }
+
+    fn check_poisoned(&self) -> Result<(), PersistError> {
+        if self.poisoned {
+            return Err(io::Error::other("the log could not be repaired after a failed write; reopen the graph").into());
+        }
+        Ok(())
+    }
+}
+
+/// A logged mutation. Every way of adding an edge is logged as the edge it adds.
+enum Record<N, T> {
+    AddNode(N),
+    AddNodeAt(N, T),
+    RemoveNodeAt(N, T),
+    AddEdge(N, TemporalEdge<N, T>),
+    RemoveNode(N),
+    RemoveEdge(N, N, T),
+    EnableInIndex,
+}
+
+impl<N: NodeKey + Codec, T: TimeSpan + Codec> Record<N, T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Record::AddNode(node) => {
+                out.push(0);
+                node.encode(out);
+            }
+            Record::AddNodeAt(node, timestamp) => {
+                out.push(1);
+                node.encode(out);
+                timestamp.encode(out);
+            }
+            Record::RemoveNodeAt(node, timestamp) => {
+                out.push(2);
+                node.encode(out);
+                timestamp.encode(out);
+            }
+            Record::AddEdge(src, edge) => {
+                out.push(3);
+                persist::write_edge(out, src, edge);
+            }
+            Record::RemoveNode(node) => {
+                out.push(4);
+                node.encode(out);
+            }
+            Record::RemoveEdge(src, dst, timestamp) => {
+                out.push(5);
+                src.encode(out);
+                dst.encode(out);
+                timestamp.encode(out);
+            }
+            Record::EnableInIndex => out.push(6),
+        }
+    }
+
+    fn decode(mut input: &[u8]) -> Result<Self, PersistError> {
+        let input = &mut input;
+        let record = match persist::take(input, 1)?[0] {
+            0 => Record::AddNode(N::decode(input)?),
+            1 => Record::AddNodeAt(N::decode(input)?, T::decode(input)?),
+            2 => Record::RemoveNodeAt(N::decode(input)?, T::decode(input)?),
+            3 => {
+                let (src, edge) = persist::read_edge(input)?;
+                Record::AddEdge(src, edge)
+            }
+            4 => Record::RemoveNode(N::decode(input)?),
+            5 => Record::RemoveEdge(N::decode(input)?, N::decode(input)?, T::decode(input)?),
+            6 => Record::EnableInIndex,
+            other => return Err(persist::corrupt(format!("unknown log record type {}", other))),
+        };
+        Ok(record)
+    }
+
+    fn apply<G: TemporalGraphOps<Node = N, Time = T>>(self, graph: &mut G) -> Result<(), GraphError<N, T>> {
+        match self {
+            Record::AddNode(node) => graph.add_node(node),
+            Record::AddNodeAt(node, timestamp) => graph.add_node_at(node, timestamp),
+            Record::RemoveNodeAt(node, timestamp) => {
+                graph.remove_node_at(node, timestamp)?;
+            }
+            Record::AddEdge(src, edge) => match edge.duration {
+                Some(duration) => graph.add_edge_with_duration(src, edge.dst, edge.interval, duration)?,
+                None => graph.add_edge_with(src, edge.dst, edge.interval, edge.weight, edge.properties)?,
+            },
+            Record::RemoveNode(node) => {
+                graph.remove_node(node)?;
+            }
+            Record::RemoveEdge(src, dst, timestamp) => {
+                graph.remove_edge(src, dst, timestamp)?;
+            }
+            Record::EnableInIndex => graph.enable_in_index(),
+        }
+        Ok(())
+    }
+}
+
+/// Frames `payload` as a log record with sequence number `lsn`.
+fn frame(lsn: u64, payload: &[u8]) -> Vec<u8> {
+    let mut checked = lsn.to_le_bytes().to_vec();
+    checked.extend_from_slice(payload);
+    let mut record = Vec::with_capacity(RECORD_HEADER_LEN + payload.len());
+    record.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    record.extend_from_slice(&crc32(&checked).to_le_bytes());
+    record.extend_from_slice(&checked);
+    record
+}
+
+/// Applies the records of `log` after `checkpoint_lsn` to `graph`.
+///
+/// Returns the LSN of the last record and the length of the log up to
+/// the end of the last intact record.
+fn replay<G>(log: &[u8], checkpoint_lsn: u64, graph: &mut G) -> Result<(u64, usize), PersistError>
+where
+    G: TemporalGraphOps,
+    G::Node: Codec,
+    G::Time: Codec + TimeSpan,
+{
+    if log.len() < LOG_HEADER_LEN || log[..LOG_MAGIC.len()] != LOG_MAGIC {
+        return Err(PersistError::BadMagic);
+    }
+    if log[LOG_MAGIC.len()] != LOG_VERSION {
+        return Err(PersistError::UnsupportedVersion { major: log[LOG_MAGIC.len()], minor: 0 });
+    }
+
+    let mut lsn = checkpoint_lsn;
+    let mut pos = LOG_HEADER_LEN;
+    while let Some(header) = log.get(pos..pos + RECORD_HEADER_LEN) {
+        let len = u32::from_le_bytes(header[..4].try_into().expect("four bytes")) as usize;
+        let stored = u32::from_le_bytes(header[4..8].try_into().expect("four bytes"));
+        let Some(checked) = log.get(pos + 8..pos + RECORD_HEADER_LEN + len) else {
+            break;
+        };
+        if crc32(checked) != stored {
+            break;
+        }
+        let record_lsn = u64::from_le_bytes(checked[..8].try_into().expect("eight bytes"));
+        if record_lsn > checkpoint_lsn {
+            if record_lsn != lsn + 1 {
+                return Err(persist::corrupt(format!("log record {} follows record {}", record_lsn, lsn)));
+            }
+            // Rejected mutations are logged too; they are rejected again here.
+            let _ = Record::<G::Node, G::Time>::decode(&checked[8..])?.apply(graph);
+            lsn = record_lsn;
+        }
+        pos += RECORD_HEADER_LEN + len;
+    }
+    Ok((lsn, pos))
+}
+
+/// Replaces the log in `dir` with an empty one.
+fn write_new_log(dir: &Path) -> Result<(), PersistError> {
+    let tmp = dir.join(format!("{}.tmp", LOG_FILE));
+    let mut file = File::create(&tmp)?;
+    file.write_all(&LOG_MAGIC)?;
+    file.write_all(&[LOG_VERSION])?;
+    file.sync_all()?;
+    fs::rename(&tmp, dir.join(LOG_FILE))?;
+    sync_dir(dir)?;
+    Ok(())
+}
+
+/// Syncs the directory entry of `dir`, making a rename in it durable.
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    File::open(dir)?.sync_all()?;
+    #[cfg(not(unix))]
+    let _ = dir;
+    Ok(())
+}
+
+/// Reads a checkpoint file, returning the graph and the LSN it covers.
+fn read_checkpoint<G: Persistent, R: Read>(mut reader: R) -> Result<(G, u64), PersistError> {
+    let mut header = [0; 16];
+    reader.read_exact(&mut header).map_err(|_| persist::corrupt("truncated checkpoint header"))?;
+    if header[..4] != CHECKPOINT_MAGIC {
+        return Err(PersistError::BadMagic);
+    }
+    let stored = u32::from_le_bytes(header[12..].try_into().expect("four bytes"));
+    let computed = crc32(&header[..12]);
+    if stored != computed {
+        return Err(PersistError::ChecksumMismatch { stored, computed });
+    }
+    let lsn = u64::from_le_bytes(header[4..12].try_into().expect("eight bytes"));
+    Ok((G::load_from(reader)?, lsn))
+}